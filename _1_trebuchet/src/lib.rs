@@ -0,0 +1,84 @@
+use aho_corasick::AhoCorasick;
+use std::collections::HashMap;
+use std::str::FromStr;
+use utils::{Error, Result, Solution};
+
+fn no_digits(line: usize) -> Error {
+    Error::Solve(format!("no digits in line {}", line + 1))
+}
+
+fn part1(lines: &[String]) -> Result<u64> {
+    let mut res = 0;
+    for (i, l) in lines.iter().enumerate() {
+        let c = l.chars().filter(|c| c.is_ascii_digit()).collect::<Vec<char>>();
+        let (first, last) = match (c.first(), c.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(no_digits(i)),
+        };
+        res += u64::from_str(&format!("{}{}", first, last)).unwrap();
+    }
+    Ok(res)
+}
+
+fn part2(lines: &[String]) -> Result<u64> {
+    let mut res = 0;
+    let pt = HashMap::from([
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+        ("1", 1),
+        ("2", 2),
+        ("3", 3),
+        ("4", 4),
+        ("5", 5),
+        ("6", 6),
+        ("7", 7),
+        ("8", 8),
+        ("9", 9),
+    ]);
+    let keys = pt.keys().collect::<Vec<&&str>>();
+    let ac = AhoCorasick::new(&keys).unwrap();
+    for (i, l) in lines.iter().enumerate() {
+        let mut mtch = Vec::with_capacity(10);
+        for mat in ac.find_overlapping_iter(l) {
+            mtch.push(mat.pattern());
+        }
+
+        let (first, last) = match (mtch.first(), mtch.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(no_digits(i)),
+        };
+        let first = pt[keys[first.as_usize()]];
+        let last = pt[keys[last.as_usize()]];
+
+        res += first * 10 + last;
+    }
+    Ok(res)
+}
+
+pub struct Trebuchet;
+
+impl Solution for Trebuchet {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<u64> {
+        part1(lines)
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<u64> {
+        part2(lines)
+    }
+}
@@ -0,0 +1,104 @@
+use utils::{Error, Result, Solution};
+
+#[derive(Default)]
+pub struct Game {
+    r: u64,
+    g: u64,
+    b: u64,
+}
+
+pub struct Record {
+    id: u64,
+    games: Vec<Game>,
+}
+
+fn parse_game(s: &str) -> Option<Record> {
+    let (head, gamelines) = s.split_once(':')?;
+    let id: u64 = head.split(' ').nth(1)?.parse().ok()?;
+    let mut games = vec![];
+    for gameline in gamelines.split(';') {
+        let mut game = Game::default();
+        let cubes: Vec<&str> = gameline.split(", ").collect();
+        for cube in cubes {
+            let mut p = cube.trim().split(' ');
+            let count: u64 = p.next()?.parse().ok()?;
+            let color = p.next()?;
+            match color {
+                "blue" => game.b = count,
+                "red" => game.r = count,
+                "green" => game.g = count,
+                _ => return None,
+            };
+        }
+        games.push(game);
+    }
+    Some(Record { id, games })
+}
+
+fn is_games_possible(games: &[Game], max_r: u64, max_g: u64, max_b: u64) -> bool {
+    for game in games {
+        if game.r > max_r || game.g > max_g || game.b > max_b {
+            return false;
+        }
+    }
+    true
+}
+
+fn possible_games_sum_part1(records: &[Record], max_r: u64, max_g: u64, max_b: u64) -> u64 {
+    let mut res = 0;
+    for record in records {
+        if is_games_possible(&record.games, max_r, max_g, max_b) {
+            res += record.id;
+        }
+    }
+    res
+}
+
+fn sum_of_power_of_min_cubes(records: &[Record]) -> u64 {
+    let mut res = 0;
+    for record in records {
+        let mut max_r = 1;
+        let mut max_g = 1;
+        let mut max_b = 1;
+        for game in &record.games {
+            if game.r > max_r {
+                max_r = game.r;
+            }
+            if game.g > max_g {
+                max_g = game.g;
+            }
+            if game.b > max_b {
+                max_b = game.b;
+            }
+        }
+        res += max_r * max_g * max_b;
+    }
+    res
+}
+
+pub struct CubeConundrum;
+
+impl Solution for CubeConundrum {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    type Parsed = Vec<Record>;
+
+    fn parse(input: &str) -> Result<Vec<Record>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| {
+                parse_game(l).ok_or_else(|| Error::Parse(format!("line {}: bad game `{}`", i + 1, l)))
+            })
+            .collect()
+    }
+
+    fn part1(records: &Vec<Record>) -> Result<u64> {
+        Ok(possible_games_sum_part1(records, 12, 13, 14))
+    }
+
+    fn part2(records: &Vec<Record>) -> Result<u64> {
+        Ok(sum_of_power_of_min_cubes(records))
+    }
+}
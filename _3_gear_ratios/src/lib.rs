@@ -0,0 +1,479 @@
+pub mod render;
+
+use log::debug;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use utils::grid::{Grid, Point};
+use utils::{Error, Result, Solution};
+
+// direction a number is read in, left to right or top to bottom
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    Row,
+    Column,
+}
+
+impl Axis {
+    // i-th cell of line n along this axis
+    fn point(self, n: usize, i: usize) -> Point {
+        match self {
+            Axis::Row => Point::new(n, i),
+            Axis::Column => Point::new(i, n),
+        }
+    }
+}
+
+// which directions the tokenizer reads numbers in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+impl Orientation {
+    fn axes(self) -> &'static [Axis] {
+        match self {
+            Orientation::Horizontal => &[Axis::Row],
+            Orientation::Vertical => &[Axis::Column],
+            Orientation::Both => &[Axis::Row, Axis::Column],
+        }
+    }
+}
+
+impl FromStr for Orientation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "horizontal" => Ok(Orientation::Horizontal),
+            "vertical" => Ok(Orientation::Vertical),
+            "both" => Ok(Orientation::Both),
+            _ => Err(Error::Parse(format!(
+                "unknown orientation `{}`, expected horizontal, vertical or both",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub start: Point,
+    pub len: usize,
+    pub axis: Axis,
+    pub value: u64,
+}
+
+impl NumberSpan {
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        let (n, i) = match self.axis {
+            Axis::Row => (self.start.row, self.start.col),
+            Axis::Column => (self.start.col, self.start.row),
+        };
+        (i..i + self.len).map(move |i| self.axis.point(n, i))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub pos: Point,
+    pub ch: char,
+}
+
+// how a number touching several symbols is counted in part 1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Multiplicity {
+    // every part number is summed once
+    #[default]
+    Once,
+    // once for every symbol it touches
+    PerSymbol,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reduction {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Reduction {
+    pub fn apply(self, values: impl IntoIterator<Item = u64>) -> Result<u64> {
+        let mut values = values.into_iter();
+        let overflow = || Error::Overflow(format!("{:?} of gear parts", self).to_lowercase());
+        match self {
+            Reduction::Product => {
+                values.try_fold(1u64, |acc, v| acc.checked_mul(v).ok_or_else(overflow))
+            }
+            Reduction::Sum => {
+                values.try_fold(0u64, |acc, v| acc.checked_add(v).ok_or_else(overflow))
+            }
+            Reduction::Max => Ok(values.max().unwrap_or(0)),
+        }
+    }
+}
+
+impl FromStr for Reduction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "product" => Ok(Reduction::Product),
+            "sum" => Ok(Reduction::Sum),
+            "max" => Ok(Reduction::Max),
+            _ => Err(Error::Parse(format!(
+                "unknown reduction `{}`, expected product, sum or max",
+                s
+            ))),
+        }
+    }
+}
+
+// which symbols are gears, how many adjacent parts they need and how
+// the parts are combined into the gear value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub parts: RangeInclusive<usize>,
+    pub reduction: Reduction,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            symbols: vec!['*'],
+            parts: 2..=2,
+            reduction: Reduction::Product,
+        }
+    }
+}
+
+// `N` for exactly N parts or `N-M` for N to M parts inclusive
+pub fn parse_arity(s: &str) -> Result<RangeInclusive<usize>> {
+    let bad = || Error::Parse(format!("bad part count `{}`, expected N or N-M", s));
+    let (lo, hi) = s.split_once('-').unwrap_or((s, s));
+    let lo: usize = lo.trim().parse().map_err(|_| bad())?;
+    let hi: usize = hi.trim().parse().map_err(|_| bad())?;
+    if lo > hi {
+        return Err(bad());
+    }
+    Ok(lo..=hi)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gear {
+    pub pos: Point,
+    pub ch: char,
+    pub parts: Vec<u64>,
+    pub value: u64,
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub numbers: Vec<NumberSpan>,
+    pub symbols: Vec<Symbol>,
+    // indexes into numbers for every symbol, and into symbols for every number
+    by_symbol: Vec<Vec<usize>>,
+    by_number: Vec<Vec<usize>>,
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
+fn read_numbers(grid: &Grid<char>, axis: Axis, numbers: &mut Vec<NumberSpan>) -> Result<()> {
+    let (lines, len) = match axis {
+        Axis::Row => (grid.height(), grid.width()),
+        Axis::Column => (grid.width(), grid.height()),
+    };
+    for n in 0..lines {
+        let mut current: Option<NumberSpan> = None;
+        for i in 0..len {
+            let pos = axis.point(n, i);
+            let Some(d) = grid[pos].to_digit(10) else {
+                numbers.extend(current.take());
+                continue;
+            };
+            let span = current.get_or_insert(NumberSpan {
+                start: pos,
+                len: 0,
+                axis,
+                value: 0,
+            });
+            span.len += 1;
+            span.value = span
+                .value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(d as u64))
+                .ok_or_else(|| {
+                    Error::Overflow(format!(
+                        "number at line {} column {}",
+                        span.start.row + 1,
+                        span.start.col + 1
+                    ))
+                })?;
+        }
+        numbers.extend(current);
+    }
+    Ok(())
+}
+
+fn tokenize(grid: &Grid<char>, orientation: Orientation) -> Result<(Vec<NumberSpan>, Vec<Symbol>)> {
+    let mut numbers = vec![];
+    for &axis in orientation.axes() {
+        read_numbers(grid, axis, &mut numbers)?;
+    }
+    // a one digit span is only part of a number when it doesn't sit inside
+    // a longer number of the other axis, a lone digit reads the same both
+    // ways and keeps only its row copy
+    if orientation == Orientation::Both {
+        let mut in_column = Grid::new(
+            grid.width(),
+            grid.height(),
+            vec![false; grid.width() * grid.height()],
+        );
+        for n in numbers
+            .iter()
+            .filter(|n| n.axis == Axis::Column && n.len > 1)
+        {
+            for p in n.cells() {
+                in_column[p] = true;
+            }
+        }
+        numbers.retain(|n| n.len > 1 || (n.axis == Axis::Row && !in_column[n.start]));
+    }
+
+    let symbols = grid
+        .iter()
+        .filter(|(_, &ch)| is_symbol(ch))
+        .map(|(pos, &ch)| Symbol { pos, ch })
+        .collect();
+    Ok((numbers, symbols))
+}
+
+impl Schematic {
+    pub fn new(grid: Grid<char>, orientation: Orientation) -> Result<Self> {
+        let (numbers, symbols) = tokenize(&grid, orientation)?;
+
+        // number index for every digit cell, one per axis
+        let mut owner = Grid::new(
+            grid.width(),
+            grid.height(),
+            vec![[None; 2]; grid.width() * grid.height()],
+        );
+        for (i, n) in numbers.iter().enumerate() {
+            for p in n.cells() {
+                owner[p][n.axis as usize] = Some(i);
+            }
+        }
+
+        // a number passing through several neighbour cells is kept once
+        let mut by_symbol = Vec::with_capacity(symbols.len());
+        let mut by_number = vec![vec![]; numbers.len()];
+        for (si, s) in symbols.iter().enumerate() {
+            let mut adjacent: Vec<usize> = grid
+                .neighbours8(s.pos)
+                .flat_map(|p| owner[p].into_iter().flatten())
+                .collect();
+            adjacent.sort_unstable();
+            adjacent.dedup();
+            for &ni in &adjacent {
+                by_number[ni].push(si);
+            }
+            by_symbol.push(adjacent);
+        }
+        debug!("{} numbers, {} symbols", numbers.len(), symbols.len());
+
+        Ok(Schematic {
+            grid,
+            numbers,
+            symbols,
+            by_symbol,
+            by_number,
+        })
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        Schematic::parse_with(s, Orientation::default())
+    }
+
+    pub fn parse_with(s: &str, orientation: Orientation) -> Result<Self> {
+        let grid = Grid::parse(s).map_err(|e| Error::Parse(format!("schematic {}", e)))?;
+        Schematic::new(grid, orientation)
+    }
+
+    pub fn numbers_around(&self, symbol: usize) -> impl Iterator<Item = &NumberSpan> {
+        self.by_symbol[symbol].iter().map(|&i| &self.numbers[i])
+    }
+
+    // number touches at least one symbol
+    pub fn is_part(&self, number: usize) -> bool {
+        !self.by_number[number].is_empty()
+    }
+
+    pub fn symbols_around(&self, number: usize) -> impl Iterator<Item = &Symbol> {
+        self.by_number[number].iter().map(|&i| &self.symbols[i])
+    }
+
+    pub fn part_numbers(&self, policy: Multiplicity) -> Vec<u64> {
+        match policy {
+            Multiplicity::Once => (0..self.numbers.len())
+                .filter(|&i| self.is_part(i))
+                .map(|i| self.numbers[i].value)
+                .collect(),
+            Multiplicity::PerSymbol => (0..self.symbols.len())
+                .flat_map(|i| self.numbers_around(i).map(|n| n.value))
+                .collect(),
+        }
+    }
+
+    pub fn gears(&self, rule: &GearRule) -> Result<Vec<Gear>> {
+        let mut res = vec![];
+        for (i, sym) in self.symbols.iter().enumerate() {
+            if !rule.symbols.contains(&sym.ch) || !rule.parts.contains(&self.by_symbol[i].len()) {
+                continue;
+            }
+            let parts: Vec<u64> = self.numbers_around(i).map(|n| n.value).collect();
+            res.push(Gear {
+                pos: sym.pos,
+                ch: sym.ch,
+                value: rule.reduction.apply(parts.iter().copied())?,
+                parts,
+            });
+        }
+        Ok(res)
+    }
+}
+
+fn sum_of_part_numbers_part1(schematic: &Schematic) -> u64 {
+    schematic.part_numbers(Multiplicity::default()).iter().sum()
+}
+
+fn engine_parts_part2(schematic: &Schematic) -> Result<u64> {
+    let gears = schematic.gears(&GearRule::default())?;
+    Reduction::Sum.apply(gears.iter().map(|g| g.value))
+}
+
+pub struct GearRatios;
+
+impl Solution for GearRatios {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    type Parsed = Schematic;
+
+    fn parse(input: &str) -> Result<Schematic> {
+        Schematic::parse(input)
+    }
+
+    fn part1(schematic: &Schematic) -> Result<u64> {
+        Ok(sum_of_part_numbers_part1(schematic))
+    }
+
+    fn part2(schematic: &Schematic) -> Result<u64> {
+        engine_parts_part2(schematic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangular_schematic() {
+        let grid = Schematic::parse("467..114..\n...*......\n..35..633.\n").unwrap();
+        assert_eq!((grid.grid.width(), grid.grid.height()), (10, 3));
+        assert_eq!(sum_of_part_numbers_part1(&grid), 467 + 35);
+        assert_eq!(engine_parts_part2(&grid).unwrap(), 467 * 35);
+
+        // numbers touching the right edge
+        let grid = Schematic::parse("..12\n...#\n").unwrap();
+        assert_eq!(sum_of_part_numbers_part1(&grid), 12);
+    }
+
+    #[test]
+    fn multiplicity() {
+        let s = Schematic::parse("617*33\n.....+\n").unwrap();
+        assert_eq!(s.numbers.len(), 2);
+        assert_eq!(s.symbols.len(), 2);
+        assert_eq!(s.symbols_around(1).count(), 2);
+        assert_eq!(s.part_numbers(Multiplicity::Once), vec![617, 33]);
+        assert_eq!(s.part_numbers(Multiplicity::PerSymbol), vec![617, 33, 33]);
+        let gears = s.gears(&GearRule::default()).unwrap();
+        assert_eq!(gears.len(), 1);
+        assert_eq!((gears[0].pos, gears[0].value), (Point::new(0, 3), 617 * 33));
+    }
+
+    #[test]
+    fn gear_rules() {
+        let s = Schematic::parse("1.2.3\n.*.#.\n4...5\n").unwrap();
+        let values = |rule: &GearRule| -> Vec<u64> {
+            s.gears(rule).unwrap().iter().map(|g| g.value).collect()
+        };
+        assert!(values(&GearRule::default()).is_empty());
+
+        let rule = GearRule {
+            symbols: vec!['*', '#'],
+            parts: parse_arity("3-4").unwrap(),
+            reduction: Reduction::Sum,
+        };
+        assert_eq!(values(&rule), vec![1 + 2 + 4, 2 + 3 + 5]);
+
+        let rule = GearRule {
+            parts: parse_arity("3").unwrap(),
+            reduction: "max".parse().unwrap(),
+            ..GearRule::default()
+        };
+        assert_eq!(values(&rule), vec![4]);
+
+        assert!(parse_arity("4-3").is_err());
+        assert!(parse_arity("two").is_err());
+        assert!("min".parse::<Reduction>().is_err());
+        assert!(Reduction::Product.apply([u64::MAX, 2]).is_err());
+    }
+
+    #[test]
+    fn orientation() {
+        let input = ".4..\n.2*3\n....\n";
+        let parts = |o: Orientation| {
+            let s = Schematic::parse_with(input, o).unwrap();
+            s.part_numbers(Multiplicity::Once)
+        };
+        assert_eq!(parts(Orientation::Horizontal), vec![4, 2, 3]);
+        assert_eq!(parts(Orientation::Vertical), vec![42, 3]);
+        assert_eq!(parts(Orientation::Both), vec![3, 42]);
+
+        let s = Schematic::parse_with(input, Orientation::Both).unwrap();
+        let cells: Vec<_> = s.numbers[1].cells().collect();
+        assert_eq!(cells, vec![Point::new(0, 1), Point::new(1, 1)]);
+        // 42 touches the symbol through two cells but is counted once
+        assert_eq!(s.numbers_around(0).count(), 2);
+
+        // a number reads the same written down or across
+        for input in [".4.\n.2*\n...\n", "42*\n...\n"] {
+            let s = Schematic::parse_with(input, Orientation::Both).unwrap();
+            assert_eq!(s.part_numbers(Multiplicity::Once), vec![42], "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn ragged_schematic() {
+        let err = Schematic::parse("467..\n...*.\n..35\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error: schematic line 3: row has 4 cells, expected 5"
+        );
+    }
+
+    // #[test]
+    // fn check_range() {
+    //     let mut count = 0;
+    //     for _i in (10..0).rev() {
+    //         count += 1;
+    //     }
+    //     assert!(count > 0);
+    // }
+}
@@ -0,0 +1,181 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use utils::grid::Grid;
+use utils::{Error, Result};
+
+use crate::{GearRule, Schematic};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ansi" => Ok(Format::Ansi),
+            "html" => Ok(Format::Html),
+            _ => Err(Error::Parse(format!(
+                "unknown format `{}`, expected ansi or html",
+                s
+            ))),
+        }
+    }
+}
+
+// what a cell is highlighted as, later variants win when a cell belongs
+// to several numbers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Mark {
+    Blank,
+    Symbol,
+    NotPart,
+    Part,
+    Gear,
+}
+
+impl Mark {
+    fn ansi(self) -> &'static str {
+        match self {
+            Mark::Blank => "\x1b[2m",
+            Mark::Symbol => "\x1b[36m",
+            Mark::NotPart => "\x1b[31m",
+            Mark::Part => "\x1b[32m",
+            Mark::Gear => "\x1b[1;33m",
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Mark::Blank => "blank",
+            Mark::Symbol => "symbol",
+            Mark::NotPart => "not-part",
+            Mark::Part => "part",
+            Mark::Gear => "gear",
+        }
+    }
+}
+
+fn marks(s: &Schematic, rule: &GearRule) -> Result<Grid<Mark>> {
+    let grid = &s.grid;
+    let mut marks = Grid::new(
+        grid.width(),
+        grid.height(),
+        vec![Mark::Blank; grid.width() * grid.height()],
+    );
+    for (i, n) in s.numbers.iter().enumerate() {
+        let mark = if s.is_part(i) {
+            Mark::Part
+        } else {
+            Mark::NotPart
+        };
+        for p in n.cells() {
+            marks[p] = marks[p].max(mark);
+        }
+    }
+    for sym in &s.symbols {
+        marks[sym.pos] = Mark::Symbol;
+    }
+    for gear in s.gears(rule)? {
+        marks[gear.pos] = Mark::Gear;
+    }
+    Ok(marks)
+}
+
+pub fn render(s: &Schematic, rule: &GearRule, format: Format) -> Result<String> {
+    let marks = marks(s, rule)?;
+    Ok(match format {
+        Format::Ansi => ansi(s, &marks),
+        Format::Html => html(s, &marks),
+    })
+}
+
+fn ansi(s: &Schematic, marks: &Grid<Mark>) -> String {
+    let mut out = String::new();
+    for (chars, marks) in s.grid.rows().zip(marks.rows()) {
+        let mut current = None;
+        for (&ch, &mark) in chars.iter().zip(marks) {
+            if current != Some(mark) {
+                out.push_str(mark.ansi());
+                current = Some(mark);
+            }
+            out.push(ch);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+const STYLE: &str = "body { background: #111; color: #ccc; }
+pre { font: 14px/1.2 monospace; }
+.blank { color: #444; }
+.symbol { color: #4cc; }
+.not-part { color: #e44; font-weight: bold; }
+.part { color: #4c4; }
+.gear { background: #cc4; color: #111; font-weight: bold; }
+";
+
+fn html(s: &Schematic, marks: &Grid<Mark>) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Gear Ratios schematic</title>\n");
+    let _ = write!(out, "<style>\n{}</style>\n</head>\n<body>\n", STYLE);
+    out.push_str("<p>");
+    for mark in [Mark::Part, Mark::NotPart, Mark::Gear, Mark::Symbol] {
+        let _ = write!(out, "<span class=\"{0}\">{0}</span> ", mark.class());
+    }
+    out.push_str("</p>\n<pre>\n");
+    for (chars, marks) in s.grid.rows().zip(marks.rows()) {
+        let mut current = None;
+        for (&ch, &mark) in chars.iter().zip(marks) {
+            if current != Some(mark) {
+                if current.is_some() {
+                    out.push_str("</span>");
+                }
+                let _ = write!(out, "<span class=\"{}\">", mark.class());
+                current = Some(mark);
+            }
+            match ch {
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '&' => out.push_str("&amp;"),
+                _ => out.push(ch),
+            }
+        }
+        out.push_str("</span>\n");
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..\n...*......\n..35..&633\n";
+
+    #[test]
+    fn ansi_runs() {
+        let s = Schematic::parse(INPUT).unwrap();
+        let out = render(&s, &GearRule::default(), Format::Ansi).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "\x1b[32m467\x1b[2m..\x1b[31m114\x1b[2m..\x1b[0m");
+        assert_eq!(lines[1], "\x1b[2m...\x1b[1;33m*\x1b[2m......\x1b[0m");
+    }
+
+    #[test]
+    fn html_escapes_and_classes() {
+        let s = Schematic::parse(INPUT).unwrap();
+        let out = render(&s, &GearRule::default(), Format::Html).unwrap();
+        assert!(out.starts_with("<!DOCTYPE html>"));
+        assert!(out.contains("<span class=\"symbol\">&amp;</span><span class=\"part\">633</span>"));
+        assert!(out.contains("<span class=\"gear\">*</span>"));
+        assert!(out.contains("<span class=\"not-part\">114</span>"));
+        assert!("svg".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use log::debug;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, space0, space1, u64},
+    combinator::cut,
+    error::context,
+    multi::many0,
+    sequence::preceded,
+};
+use utils::parse::{finish, IResult};
+use utils::{Error, Result, Solution};
+
+#[derive(Debug)]
+pub struct Card {
+    id: u64,
+    winning: Vec<u64>,
+    have: Vec<u64>,
+}
+
+fn parse_card_id(s: &str) -> IResult<'_, u64> {
+    preceded(context("`Card`", tag("Card")), preceded(space1, u64))(s)
+}
+
+// numbers may be separated by any run of spaces or tabs
+fn parse_single_card(s: &str) -> IResult<'_, Card> {
+    let (s, id) = parse_card_id(s)?;
+    let (s, _) = cut(preceded(space0, char(':')))(s)?;
+    let (s, winning) = many0(preceded(space0, u64))(s)?;
+    let (s, _) = cut(context("`|`", preceded(space0, char('|'))))(s)?;
+    let (s, have) = many0(preceded(space0, u64))(s)?;
+    let (s, _) = space0(s)?;
+    Ok((s, Card { id, winning, have }))
+}
+
+// parses line by line so a bad card stops parsing with its number instead
+// of silently dropping the rest of the file, blank lines are skipped
+fn parse_cards(s: &str) -> Result<Vec<Card>> {
+    let mut cards = vec![];
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let card = finish(line, parse_single_card).map_err(|mut e| {
+            e.line = i + 1;
+            match parse_card_id(line) {
+                Ok((_, id)) => e.within(format!("card {}", id)),
+                Err(_) => e,
+            }
+        })?;
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+fn cards_points_part1(cards: &[Card]) -> u64 {
+    let mut res = 0;
+    for card in cards {
+        let matches = card.matches();
+        if matches == 0 {
+            continue;
+        }
+        res += 2u64.pow((matches - 1) as u32);
+    }
+    res
+}
+
+impl Card {
+    // distinct numbers on both sides, a number listed twice matches once
+    pub fn matches(&self) -> usize {
+        let win: HashSet<u64> = self.winning.iter().copied().collect();
+        let have: HashSet<u64> = self.have.iter().copied().collect();
+        have.intersection(&win).count()
+    }
+}
+
+// what happens to copies won of cards past the end of the table
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PastEnd {
+    // drop them
+    Clamp,
+    // the puzzle promises it never happens
+    #[default]
+    Error,
+    // add cards without matches to the end of the table
+    Extend,
+}
+
+impl FromStr for PastEnd {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "clamp" => Ok(PastEnd::Clamp),
+            "error" => Ok(PastEnd::Error),
+            "extend" => Ok(PastEnd::Extend),
+            _ => Err(Error::Parse(format!(
+                "unknown policy `{}`, expected clamp, error or extend",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CardStats {
+    pub id: u64,
+    pub matches: usize,
+    pub instances: u64,
+    pub copies_won: u64,
+    pub copies_received: u64,
+}
+
+// instance counts of every card after all copies are handed out, indexed
+// by position in the table
+pub struct CardCascade {
+    first_id: u64,
+    // cards given in the input, the rest come from PastEnd::Extend
+    given: usize,
+    matches: Vec<usize>,
+    received: Vec<u64>,
+    won: Vec<u64>,
+}
+
+impl CardCascade {
+    pub fn new(cards: &[Card], policy: PastEnd) -> Result<Self> {
+        let first_id = cards.first().map_or(1, |c| c.id);
+        for (i, card) in cards.iter().enumerate() {
+            if card.id != first_id + i as u64 {
+                return Err(Error::Parse(format!(
+                    "card {} found where card {} was expected",
+                    card.id,
+                    first_id + i as u64
+                )));
+            }
+        }
+
+        let overflow = |id: u64| Error::Overflow(format!("copies of card {}", id));
+        let mut matches: Vec<usize> = cards.iter().map(Card::matches).collect();
+        let mut received = vec![0u64; cards.len()];
+        let mut won = vec![0u64; cards.len()];
+        let mut i = 0;
+        while i < matches.len() {
+            let id = first_id + i as u64;
+            let instances = received[i] + (i < cards.len()) as u64;
+            let mut wins = matches[i];
+            debug!("have {} wins for card {}", wins, id);
+            if i + wins >= matches.len() {
+                match policy {
+                    PastEnd::Clamp => wins = matches.len() - i - 1,
+                    PastEnd::Error => {
+                        return Err(Error::Solve(format!(
+                            "card {} wins copies up to card {}, past the last card {}",
+                            id,
+                            id + wins as u64,
+                            first_id + matches.len() as u64 - 1
+                        )))
+                    }
+                    PastEnd::Extend => {
+                        matches.resize(i + wins + 1, 0);
+                        received.resize(i + wins + 1, 0);
+                        won.resize(i + wins + 1, 0);
+                    }
+                }
+            }
+            for r in &mut received[i + 1..=i + wins] {
+                *r = r.checked_add(instances).ok_or_else(|| overflow(id))?;
+            }
+            won[i] = instances
+                .checked_mul(wins as u64)
+                .ok_or_else(|| overflow(id))?;
+            i += 1;
+        }
+
+        Ok(CardCascade {
+            first_id,
+            given: cards.len(),
+            matches,
+            received,
+            won,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+
+    pub fn stats(&self, i: usize) -> CardStats {
+        CardStats {
+            id: self.first_id + i as u64,
+            matches: self.matches[i],
+            instances: self.received[i] + (i < self.given) as u64,
+            copies_won: self.won[i],
+            copies_received: self.received[i],
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CardStats> + '_ {
+        (0..self.len()).map(|i| self.stats(i))
+    }
+
+    pub fn total(&self) -> Result<u64> {
+        self.iter().try_fold(0u64, |acc, c| {
+            acc.checked_add(c.instances)
+                .ok_or_else(|| Error::Overflow("total card instances".to_string()))
+        })
+    }
+}
+
+pub struct Scratchcards;
+
+impl Solution for Scratchcards {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Vec<Card>> {
+        let cards = parse_cards(input)?;
+        if cards.is_empty() {
+            return Err(Error::Parse("no cards found".to_string()));
+        }
+        Ok(cards)
+    }
+
+    fn part1(cards: &Vec<Card>) -> Result<u64> {
+        Ok(cards_points_part1(cards))
+    }
+
+    fn part2(cards: &Vec<Card>) -> Result<u64> {
+        CardCascade::new(cards, PastEnd::default())?.total()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let (_, card) = parse_single_card("Card 1: 11 | 12").unwrap();
+        assert_eq!(card.id, 1);
+        assert_eq!(&card.winning[..], &[11]);
+        assert_eq!(&card.have[..], &[12]);
+
+        let (_, card) = parse_single_card("Card 1:  1 | 12").unwrap();
+        assert_eq!(card.id, 1);
+        assert_eq!(&card.winning[..], &[1]);
+        assert_eq!(&card.have[..], &[12]);
+
+        let (_, card) = parse_single_card("Card  18: 61 30 69 53 76 32 29 45 26 79 | 60 69 82 78 31 72 91 13 92 40 24 54 25 14 22 87 70 35 18  7 96 86 49 15 66").unwrap();
+        assert_eq!(card.id, 18);
+        assert_eq!(card.winning.len(), 10);
+        assert_eq!(card.have.len(), 25);
+
+        let cards = parse_cards("Card 1: 11 | 12\nCard 2:  3 | 14").unwrap();
+        assert_eq!(cards[0].id, 1);
+        assert_eq!(&cards[0].winning[..], &[11]);
+        assert_eq!(&cards[0].have[..], &[12]);
+        assert_eq!(cards[1].id, 2);
+        assert_eq!(&cards[1].winning[..], &[3]);
+        assert_eq!(&cards[1].have[..], &[14]);
+    }
+
+    #[test]
+    fn whitespace() {
+        let cards = parse_cards("Card   1:  41 48   |  83  86 \r\nCard 2:1|2\t3\r\n\r\n").unwrap();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(&cards[0].winning[..], &[41, 48]);
+        assert_eq!(&cards[0].have[..], &[83, 86]);
+        assert_eq!(cards[1].id, 2);
+        assert_eq!(&cards[1].winning[..], &[1]);
+        assert_eq!(&cards[1].have[..], &[2, 3]);
+    }
+
+    #[test]
+    fn malformed_cards() {
+        let e = match parse_cards("Card 1: 11 | 12\nCard 2: 3 x 4 | 14\n") {
+            Err(Error::Syntax(e)) => e,
+            _ => panic!("expected syntax error"),
+        };
+        assert_eq!(
+            (e.line, e.column, e.item),
+            (2, 11, Some("card 2".to_string()))
+        );
+
+        // first line of the message, the rest is the offending line
+        let err = |s| {
+            let e = parse_cards(s).unwrap_err().to_string();
+            e.lines().next().unwrap().to_string()
+        };
+
+        assert_eq!(
+            err("Card 1: 11 | 12\nCard 2: 3 x 4 | 14\nCard 3: 1 | 2\n"),
+            "parse error: card 2: line 2, column 11: expected `|`"
+        );
+        assert_eq!(
+            err("Card 1: 11 | 12 x\nCard 2: 3 | 14\n"),
+            "parse error: card 1: line 1, column 17: expected end of input"
+        );
+        assert_eq!(
+            err("Card 1: 11 | 12\nCard x: 3 | 14\n"),
+            "parse error: line 2, column 6: expected number"
+        );
+        assert_eq!(
+            err("Card 1: 11 | 12\nCrad 2: 3 | 14\n"),
+            "parse error: line 2, column 1: expected `Card`"
+        );
+    }
+
+    #[test]
+    fn cascade() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+        let cards = parse_cards(input).unwrap();
+        let cascade = CardCascade::new(&cards, PastEnd::Error).unwrap();
+        assert_eq!(cascade.total().unwrap(), 30);
+        let instances: Vec<_> = cascade.iter().map(|c| c.instances).collect();
+        assert_eq!(instances, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(
+            cascade.stats(1),
+            CardStats {
+                id: 2,
+                matches: 2,
+                instances: 2,
+                copies_won: 4,
+                copies_received: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn duplicate_numbers() {
+        let input = "Card 1: 1 2 | 1 1 1\nCard 2: 3 | 4\nCard 3: 5 | 6\nCard 4: 7 | 8\n";
+        let cards = parse_cards(input).unwrap();
+        assert_eq!(cards[0].matches(), 1);
+        assert_eq!(cards_points_part1(&cards), 1);
+        let cascade = CardCascade::new(&cards, PastEnd::Error).unwrap();
+        assert_eq!(cascade.total().unwrap(), 5);
+    }
+
+    #[test]
+    fn past_end() {
+        let cards = parse_cards("Card 7: 1 2 | 3\nCard 8: 1 2 | 1 2\nCard 9: 5 | 6\n").unwrap();
+        assert!(matches!(
+            CardCascade::new(&cards, PastEnd::Error),
+            Err(Error::Solve(_))
+        ));
+
+        let clamped = CardCascade::new(&cards, PastEnd::Clamp).unwrap();
+        assert_eq!(clamped.len(), 3);
+        assert_eq!(clamped.stats(1).copies_won, 1);
+        assert_eq!(clamped.total().unwrap(), 4);
+
+        let extended = CardCascade::new(&cards, PastEnd::Extend).unwrap();
+        assert_eq!(extended.len(), 4);
+        assert_eq!(extended.stats(3).id, 10);
+        assert_eq!(extended.stats(3).instances, 1);
+        assert_eq!(extended.total().unwrap(), 5);
+
+        let gap = parse_cards("Card 1: 1 | 2\nCard 3: 1 | 2\n").unwrap();
+        assert!(CardCascade::new(&gap, PastEnd::Clamp).is_err());
+        assert!("wrap".parse::<PastEnd>().is_err());
+    }
+}
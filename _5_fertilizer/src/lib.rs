@@ -0,0 +1,637 @@
+use std::ops::Range;
+
+use log::debug;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char, newline, u64},
+    combinator::{cut, eof, opt},
+    error::context,
+    multi::{many0, many1, many_till},
+    sequence::{delimited, preceded, separated_pair, terminated},
+};
+use utils::parse::{finish, IResult};
+use utils::{Error, Result, Solution};
+
+// part of a source range together with its image
+type Piece = (Range<u64>, Range<u64>);
+
+#[derive(Debug, Clone)]
+struct Ranges {
+    dst: Range<u64>,
+    src: Range<u64>,
+}
+
+impl Ranges {
+    fn map(&self, src: u64) -> Option<u64> {
+        if !self.src.contains(&src) {
+            return None;
+        }
+        Some(self.dst.start + (src - self.src.start))
+    }
+
+    // returns part of r covered by src together with its image in dst and
+    // parts of r outside of src
+    fn map_range(&self, r: &Range<u64>) -> (Option<Piece>, Vec<Range<u64>>) {
+        let start = r.start.max(self.src.start);
+        let end = r.end.min(self.src.end);
+        if start >= end {
+            return (None, vec![r.clone()]);
+        }
+
+        let mut rest = vec![];
+        if r.start < start {
+            rest.push(r.start..start);
+        }
+        if end < r.end {
+            rest.push(end..r.end);
+        }
+        let offset = self.dst.start;
+        let mapped = offset + (start - self.src.start)..offset + (end - self.src.start);
+        (Some((start..end, mapped)), rest)
+    }
+}
+
+// sorts ranges and merges overlapping or adjacent ones
+fn normalize(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_by_key(|r| r.start);
+    let mut res: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match res.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => res.push(r),
+        }
+    }
+    res
+}
+
+const DOMAIN: Range<u64> = 0..u64::MAX;
+
+#[derive(Debug, Clone)]
+struct MapRanges(Vec<Ranges>);
+
+impl MapRanges {
+    fn map(&self, src: u64) -> u64 {
+        for range in &self.0 {
+            if let Some(k) = range.map(src) {
+                return k;
+            }
+        }
+        src
+    }
+
+    // splits r into pieces that are moved as a whole, returning every piece
+    // together with its image
+    fn split_range(&self, r: &Range<u64>) -> Vec<Piece> {
+        let mut pending = vec![r.clone()];
+        let mut res = vec![];
+        for range in &self.0 {
+            let mut rest = vec![];
+            for r in &pending {
+                let (mapped, left) = range.map_range(r);
+                res.extend(mapped);
+                rest.extend(left);
+            }
+            pending = rest;
+        }
+        // values not covered by any range map to themselves
+        res.extend(pending.into_iter().map(|r| (r.clone(), r)));
+        res
+    }
+
+    fn map_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let res = ranges
+            .iter()
+            .flat_map(|r| self.split_range(r))
+            .map(|(_, image)| image)
+            .collect();
+        normalize(res)
+    }
+
+    // single map doing the same as applying self and then next
+    fn compose(&self, next: &MapRanges) -> MapRanges {
+        let mut res = vec![];
+        for (src, image) in self.split_range(&DOMAIN) {
+            for (part, dst) in next.split_range(&image) {
+                let start = src.start + (part.start - image.start);
+                let src = start..start + (part.end - part.start);
+                if src != dst {
+                    res.push(Ranges { dst, src });
+                }
+            }
+        }
+        res.sort_by_key(|r| r.src.start);
+        MapRanges(res)
+    }
+
+    // every value that is mapped into one of targets
+    fn preimage(&self, targets: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut res = vec![];
+        for (src, image) in self.split_range(&DOMAIN) {
+            for t in targets {
+                let start = image.start.max(t.start);
+                let end = image.end.min(t.end);
+                if start < end {
+                    let offset = src.start;
+                    res.push(offset + (start - image.start)..offset + (end - image.start));
+                }
+            }
+        }
+        normalize(res)
+    }
+}
+
+#[derive(Debug)]
+struct CategoryMap {
+    from: String,
+    to: String,
+    ranges: MapRanges,
+}
+
+// `dst src len` as written, the ranges are checked when the almanac is built
+type RangeSpec = (u64, u64, u64);
+
+#[derive(Debug)]
+struct MapSpec {
+    from: String,
+    to: String,
+    ranges: Vec<RangeSpec>,
+}
+
+fn parse_range(s: &str) -> IResult<'_, RangeSpec> {
+    let (s, dst) = context("range `dst src len`", u64)(s)?;
+    let (s, src) = cut(preceded(char(' '), u64))(s)?;
+    let (s, size) = cut(preceded(char(' '), u64))(s)?;
+    Ok((s, (dst, src, size)))
+}
+
+// ranges up to an empty line or the end of input
+fn parse_map(s: &str) -> IResult<'_, Vec<RangeSpec>> {
+    let (s, (ranges, _)) =
+        many_till(terminated(parse_range, opt(newline)), alt((tag("\n"), eof)))(s)?;
+    Ok((s, ranges))
+}
+
+fn parse_category_map(s: &str) -> IResult<'_, MapSpec> {
+    let (s, (from, to)) = context(
+        "map header `a-to-b map:`",
+        terminated(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map:\n")),
+    )(s)?;
+    let (s, ranges) = terminated(parse_map, many0(newline))(s)?;
+    Ok((
+        s,
+        MapSpec {
+            from: from.to_string(),
+            to: to.to_string(),
+            ranges,
+        },
+    ))
+}
+
+fn parse_seeds(s: &str) -> IResult<'_, Vec<u64>> {
+    delimited(
+        tag("seeds:"),
+        many0(preceded(char(' '), u64)),
+        many1(newline),
+    )(s)
+}
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<CategoryMap>,
+}
+
+impl Almanac {
+    fn new(seeds: Vec<u64>, specs: Vec<MapSpec>) -> Result<Self> {
+        if specs.is_empty() {
+            return Err(Error::Parse("almanac has no maps".to_string()));
+        }
+        for w in specs.windows(2) {
+            if w[0].to != w[1].from {
+                return Err(Error::Parse(format!(
+                    "{}-to-{} map does not continue {}-to-{} map",
+                    w[1].from, w[1].to, w[0].from, w[0].to
+                )));
+            }
+        }
+
+        let mut maps = Vec::with_capacity(specs.len());
+        for m in specs {
+            let mut ranges = Vec::with_capacity(m.ranges.len());
+            for &(dst, src, len) in &m.ranges {
+                let end = |start: u64| {
+                    start.checked_add(len).ok_or_else(|| {
+                        Error::Overflow(format!(
+                            "{}-to-{} map range `{} {} {}` ends past {}",
+                            m.from,
+                            m.to,
+                            dst,
+                            src,
+                            len,
+                            u64::MAX
+                        ))
+                    })
+                };
+                ranges.push(Ranges {
+                    dst: dst..end(dst)?,
+                    src: src..end(src)?,
+                });
+            }
+            maps.push(CategoryMap {
+                from: m.from,
+                to: m.to,
+                ranges: MapRanges(ranges),
+            });
+        }
+        Ok(Almanac { seeds, maps })
+    }
+
+    fn location(&self, seed: u64) -> u64 {
+        let mut loc = seed;
+        for map in &self.maps {
+            loc = map.ranges.map(loc);
+        }
+        loc
+    }
+
+    fn source(&self) -> &str {
+        &self.maps[0].from
+    }
+
+    fn target(&self) -> &str {
+        &self.maps[self.maps.len() - 1].to
+    }
+
+    // the whole pipeline as a single map
+    fn compose(&self) -> MapRanges {
+        let mut maps = self.maps.iter().map(|m| &m.ranges);
+        let first = maps.next().unwrap().clone();
+        maps.fold(first, |acc, m| acc.compose(m))
+    }
+
+    // all source values which end up in one of targets
+    fn preimage(&self, targets: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut ranges = normalize(targets.to_vec());
+        for map in self.maps.iter().rev() {
+            ranges = map.ranges.preimage(&ranges);
+        }
+        ranges
+    }
+
+    // listed seeds which end up at the given location
+    pub fn seeds_for_location(&self, location: u64) -> Result<Vec<u64>> {
+        let end = location.checked_add(1).ok_or_else(|| {
+            Error::Overflow(format!("location {} is past the mapped values", location))
+        })?;
+        let preimage = self.preimage(&[Range {
+            start: location,
+            end,
+        }]);
+        Ok(self
+            .seeds
+            .iter()
+            .copied()
+            .filter(|s| preimage.iter().any(|r| r.contains(s)))
+            .collect())
+    }
+}
+
+fn parse_almanac(s: &str) -> IResult<'_, (Vec<u64>, Vec<MapSpec>)> {
+    let (s, seeds) = parse_seeds(s)?;
+    let (s, (maps, _)) = many_till(parse_category_map, eof)(s)?;
+    Ok((s, (seeds, maps)))
+}
+
+fn lowest_location_part1(almanac: &Almanac) -> u64 {
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.location(seed))
+        .min()
+        .unwrap_or(u64::MAX)
+}
+
+fn seed_ranges(seeds: &[u64]) -> Result<Vec<Range<u64>>> {
+    seeds
+        .chunks_exact(2)
+        .map(|p| {
+            let end = p[0].checked_add(p[1]).ok_or_else(|| {
+                Error::Overflow(format!(
+                    "seed range `{} {}` ends past {}",
+                    p[0],
+                    p[1],
+                    u64::MAX
+                ))
+            })?;
+            Ok(p[0]..end)
+        })
+        .collect()
+}
+
+// moves whole seed ranges through the pipeline, splitting them at mapping
+// boundaries, instead of mapping every single seed
+fn lowest_location_part2(almanac: &Almanac) -> Result<u64> {
+    let ranges = almanac
+        .compose()
+        .map_ranges(&normalize(seed_ranges(&almanac.seeds)?));
+    Ok(ranges.first().map(|r| r.start).unwrap_or(u64::MAX))
+}
+
+pub struct Fertilizer;
+
+impl Solution for Fertilizer {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    type Parsed = Almanac;
+
+    fn parse(input: &str) -> Result<Almanac> {
+        let (seeds, maps) = finish(input, parse_almanac)?;
+        let almanac = Almanac::new(seeds, maps)?;
+        debug!("almanac maps {} to {}", almanac.source(), almanac.target());
+        Ok(almanac)
+    }
+
+    fn part1(almanac: &Almanac) -> Result<u64> {
+        Ok(lowest_location_part1(almanac))
+    }
+
+    fn part2(almanac: &Almanac) -> Result<u64> {
+        if !almanac.seeds.chunks_exact(2).remainder().is_empty() {
+            return Err(Error::Solve("seed ranges must come in pairs".to_string()));
+        }
+        lowest_location_part2(almanac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn parser() {
+        let (_, range) = parse_range("50 98 2").unwrap();
+        assert_eq!(range, (50, 98, 2));
+
+        let (_, ranges) = parse_map("50 98 2\n50 98 2\n").unwrap();
+        assert_eq!(ranges.len(), 2);
+
+        let (_, ranges) = parse_map("50 98 2\n50 98 2").unwrap();
+        assert_eq!(ranges.len(), 2);
+    }
+
+    #[test]
+    fn malformed_almanac() {
+        let err = |s| finish(s, parse_almanac).unwrap_err();
+
+        let e = err("seeds: 1 2\n\na-to-b map:\n1 2 3\n4 5 x\n6 7 8\n");
+        assert_eq!((e.line, e.column, e.expected.as_str()), (5, 5, "number"));
+        assert_eq!(e.text, "4 5 x");
+
+        let e = err("seeds: 1 2\n\na-to-b map:\n1 2 3\n\nb-to-c map\n1 2 3\n");
+        assert_eq!((e.line, e.column), (6, 7));
+        assert_eq!(e.expected, "map header `a-to-b map:`");
+
+        let e = err("seeds: 1 x\n\na-to-b map:\n1 2 3\n");
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 9, "`\\n`"));
+    }
+
+    #[test]
+    fn overflow() {
+        let max = u64::MAX;
+        let input = format!("seeds: 1 2\n\na-to-b map:\n0 {} 2\n", max - 1);
+        match Fertilizer::parse(&input) {
+            Err(Error::Overflow(msg)) => assert_eq!(
+                msg,
+                format!("a-to-b map range `0 {} 2` ends past {}", max - 1, max)
+            ),
+            _ => panic!("expected overflow"),
+        }
+        let input = format!("seeds: 1 2\n\na-to-b map:\n{} 0 2\n", max);
+        assert!(matches!(Fertilizer::parse(&input), Err(Error::Overflow(_))));
+        // a range may end right at the largest value
+        let input = format!("seeds: 1 2\n\na-to-b map:\n0 {} 2\n", max - 2);
+        assert!(Fertilizer::parse(&input).is_ok());
+
+        let input = format!("seeds: {} 2\n\na-to-b map:\n0 10 2\n", max - 1);
+        let almanac = Fertilizer::parse(&input).unwrap();
+        assert_eq!(Fertilizer::part1(&almanac).unwrap(), 2);
+        assert!(matches!(
+            Fertilizer::part2(&almanac),
+            Err(Error::Overflow(_))
+        ));
+        assert!(matches!(
+            almanac.seeds_for_location(max),
+            Err(Error::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn generic_almanac() {
+        let input = "seeds: 1 7\n\nfoo-to-bar map:\n10 0 5\n\nbar-to-baz map:\n0 10 2\n";
+        let almanac = Fertilizer::parse(input).unwrap();
+        assert_eq!(almanac.source(), "foo");
+        assert_eq!(almanac.target(), "baz");
+        assert_eq!(almanac.location(1), 1);
+        assert_eq!(almanac.location(3), 13);
+        assert_eq!(almanac.location(7), 7);
+
+        let input = "seeds: 1\n\nfoo-to-bar map:\n10 0 5\n\nbaz-to-qux map:\n0 10 2\n";
+        assert!(Fertilizer::parse(input).is_err());
+    }
+
+    #[test]
+    fn compose_and_invert() {
+        let almanac = Fertilizer::parse(include_str!("../test_input")).unwrap();
+        assert_eq!(almanac.source(), "seed");
+        assert_eq!(almanac.target(), "location");
+
+        let map = almanac.compose();
+        for x in 0..120 {
+            assert_eq!(map.map(x), almanac.location(x), "seed {}", x);
+        }
+
+        assert_eq!(almanac.seeds_for_location(35).unwrap(), vec![13]);
+        assert_eq!(almanac.seeds_for_location(82).unwrap(), vec![79]);
+        assert!(almanac.seeds_for_location(36).unwrap().is_empty());
+
+        for loc in [0, 35, 46, 60, 99] {
+            let preimage = almanac.preimage(&[Range {
+                start: loc,
+                end: loc + 1,
+            }]);
+            for x in 0..120 {
+                assert_eq!(
+                    preimage.iter().any(|r| r.contains(&x)),
+                    almanac.location(x) == loc,
+                    "seed {} location {}",
+                    x,
+                    loc
+                );
+            }
+        }
+    }
+
+    fn lowest_location_part2_brute(almanac: &Almanac) -> u64 {
+        let mut res = u64::MAX;
+        for seed_range in seed_ranges(&almanac.seeds).unwrap() {
+            for seed in seed_range {
+                res = res.min(almanac.location(seed));
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn map_range_split() {
+        let r = Ranges {
+            dst: 50..52,
+            src: 98..100,
+        };
+        let (mapped, rest) = r.map_range(&(90..95));
+        assert_eq!(mapped, None);
+        assert_eq!(rest, [Range { start: 90, end: 95 }]);
+
+        let (mapped, rest) = r.map_range(&(98..100));
+        assert_eq!(mapped, Some((98..100, 50..52)));
+        assert!(rest.is_empty());
+
+        let (mapped, rest) = r.map_range(&(97..101));
+        assert_eq!(mapped, Some((98..100, 50..52)));
+        assert_eq!(rest, [97..98, 100..101]);
+
+        let (mapped, rest) = r.map_range(&(99..105));
+        assert_eq!(mapped, Some((99..100, 51..52)));
+        assert_eq!(
+            rest,
+            [Range {
+                start: 100,
+                end: 105
+            }]
+        );
+
+        assert_eq!(
+            normalize(vec![5..7, 1..3, 3..4, 6..9, 10..10]),
+            vec![1..4, 5..9]
+        );
+    }
+
+    #[test]
+    fn part2_matches_brute_force() {
+        let input = include_str!("../test_input");
+        let almanac = Fertilizer::parse(input).unwrap();
+        assert_eq!(lowest_location_part2(&almanac).unwrap(), 46);
+        assert_eq!(
+            lowest_location_part2(&almanac).unwrap(),
+            lowest_location_part2_brute(&almanac)
+        );
+
+        // every seed range on its own should agree as well
+        for range in seed_ranges(&almanac.seeds).unwrap() {
+            let mut single = Fertilizer::parse(input).unwrap();
+            single.seeds = vec![range.start, range.end - range.start];
+            assert_eq!(
+                lowest_location_part2(&single).unwrap(),
+                lowest_location_part2_brute(&single)
+            );
+        }
+    }
+
+    #[test]
+    fn range_bounds() {
+        let r = Ranges {
+            dst: 50..52,
+            src: 98..100,
+        };
+        assert_eq!(r.map(97), None);
+        assert_eq!(r.map(98), Some(50));
+        assert_eq!(r.map(99), Some(51));
+        assert_eq!(r.map(100), None);
+
+        // destination larger than source must not widen the source range
+        let r = Ranges {
+            dst: 100..110,
+            src: 0..5,
+        };
+        assert_eq!(r.map(4), Some(104));
+        assert_eq!(r.map(5), None);
+        assert_eq!(r.map(7), None);
+    }
+
+    // non-overlapping source ranges laid out left to right with random gaps,
+    // destinations are arbitrary
+    fn map_ranges_strategy() -> impl Strategy<Value = MapRanges> {
+        prop::collection::vec((0u64..10, 1u64..10, 0u64..200), 0..8).prop_map(|specs| {
+            let mut start = 0;
+            let mut ranges = vec![];
+            for (gap, len, dst) in specs {
+                start += gap;
+                ranges.push(Ranges {
+                    dst: dst..dst + len,
+                    src: start..start + len,
+                });
+                start += len;
+            }
+            MapRanges(ranges)
+        })
+    }
+
+    fn lookup_table(map: &MapRanges) -> HashMap<u64, u64> {
+        let mut table = HashMap::new();
+        for r in &map.0 {
+            for (s, d) in r.src.clone().zip(r.dst.clone()) {
+                table.insert(s, d);
+            }
+        }
+        table
+    }
+
+    proptest! {
+        #[test]
+        fn map_matches_lookup_table(map in map_ranges_strategy()) {
+            let table = lookup_table(&map);
+            for x in 0..150 {
+                prop_assert_eq!(map.map(x), *table.get(&x).unwrap_or(&x), "value {}", x);
+            }
+        }
+
+        #[test]
+        fn map_ranges_matches_pointwise(
+            map in map_ranges_strategy(),
+            start in 0u64..100,
+            len in 0u64..50,
+        ) {
+            let seeds = Range { start, end: start + len };
+            let mapped = map.map_ranges(std::slice::from_ref(&seeds));
+            let mut expected: Vec<u64> = seeds.map(|x| map.map(x)).collect();
+            expected.sort();
+            expected.dedup();
+            let got: Vec<u64> = mapped.into_iter().flatten().collect();
+            prop_assert_eq!(got, expected);
+        }
+
+        #[test]
+        fn compose_matches_sequential(a in map_ranges_strategy(), b in map_ranges_strategy()) {
+            let composed = a.compose(&b);
+            for x in 0..150 {
+                prop_assert_eq!(composed.map(x), b.map(a.map(x)), "value {}", x);
+            }
+        }
+
+        #[test]
+        fn preimage_matches_pointwise(map in map_ranges_strategy(), start in 0u64..150, len in 0u64..20) {
+            let preimage = map.preimage(&[Range { start, end: start + len }]);
+            for x in 0..150 {
+                let y = map.map(x);
+                prop_assert_eq!(
+                    preimage.iter().any(|r| r.contains(&x)),
+                    (start..start + len).contains(&y),
+                    "value {}", x
+                );
+            }
+        }
+    }
+}
@@ -1,190 +1,12 @@
-use std::ops::Range;
+use std::process::ExitCode;
 
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, newline, u64},
-    combinator::opt,
-    multi::{many0, many1},
-    sequence::{delimited, pair, preceded, terminated},
-    IResult,
-};
-use utils::get_file_string;
+use _5_fertilizer::Fertilizer;
+use utils::{run_inputs, solve};
 
-struct Ranges {
-    dst: Range<u64>,
-    src: Range<u64>,
-}
-
-impl Ranges {
-    fn map(&self, src: u64) -> Option<u64> {
-        let s = src.checked_sub(self.src.start);
-        if s.is_none() {
-            return None;
-        }
-        let s = self.dst.start + s.unwrap();
-        if s > self.dst.end {
-            return None;
-        }
-        Some(s)
-    }
-}
-
-struct MapRanges(Vec<Ranges>);
-
-impl MapRanges {
-    fn map(&self, src: u64) -> u64 {
-        for range in &self.0 {
-            if let Some(k) = range.map(src) {
-                return k;
-            }
-        }
-        return src;
-    }
-}
-
-fn parse_range(s: &str) -> IResult<&str, (Range<u64>, Range<u64>)> {
-    let (s, dst) = terminated(u64, char(' '))(s)?;
-    let (s, src) = terminated(u64, char(' '))(s)?;
-    let (s, size) = u64(s)?;
-    Ok((
-        s,
-        (
-            Range {
-                start: dst,
-                end: dst + size,
-            },
-            Range {
-                start: src,
-                end: src + size,
-            },
-        ),
-    ))
-}
-
-fn parse_map(s: &str) -> IResult<&str, MapRanges> {
-    let (s, ranges) = many0(terminated(parse_range, opt(newline)))(s)?;
-    Ok((
-        s,
-        MapRanges(
-            ranges
-                .into_iter()
-                .map(|(dst, src)| Ranges { dst, src })
-                .collect(),
-        ),
-    ))
-}
-
-fn parse_map_with_tag<'a>(t: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, MapRanges> {
-    delimited(tag(t), parse_map, many0(newline))
-}
-
-fn parse_input(s: &str) -> IResult<&str, Vec<MapRanges>> {
-    let (s, seed_to_soil) = parse_map_with_tag("seed-to-soil map:\n")(s)?;
-    let (s, soild_to_fertilizer) = parse_map_with_tag("soil-to-fertilizer map:\n")(s)?;
-    let (s, fertilizer_to_water) = parse_map_with_tag("fertilizer-to-water map:\n")(s)?;
-    let (s, water_to_light) = parse_map_with_tag("water-to-light map:\n")(s)?;
-    let (s, light_to_temperature) = parse_map_with_tag("light-to-temperature map:\n")(s)?;
-    let (s, temperature_to_humidity) = parse_map_with_tag("temperature-to-humidity map:\n")(s)?;
-    let (s, humidity_to_location) = parse_map_with_tag("humidity-to-location map:\n")(s)?;
-
-    Ok((
-        s,
-        vec![
-            seed_to_soil,
-            soild_to_fertilizer,
-            fertilizer_to_water,
-            water_to_light,
-            light_to_temperature,
-            temperature_to_humidity,
-            humidity_to_location,
-        ],
-    ))
-}
-
-fn parse_seeds(s: &str) -> IResult<&str, Vec<u64>> {
-    delimited(
-        tag("seeds:"),
-        many0(preceded(char(' '), u64)),
-        many1(newline),
-    )(s)
-}
-
-fn lowest_location_part1(s: &str) -> u64 {
-    let (s, seeds) = parse_seeds(s).unwrap();
-    let (_, maps) = parse_input(s).unwrap();
-    let mut res = u64::MAX;
-    for seed in seeds {
-        let mut loc = seed;
-        for map in &maps {
-            loc = map.map(loc);
-        }
-        if loc < res {
-            res = loc;
-        }
-    }
-    res
-}
-
-fn parse_seeds_range(s: &str) -> IResult<&str, Vec<Range<u64>>> {
-    let (s, seeds) = delimited(
-        tag("seeds:"),
-        many1(pair(preceded(char(' '), u64), preceded(char(' '), u64))),
-        many1(newline),
-    )(s)?;
-    Ok((
-        s,
-        seeds
-            .into_iter()
-            .map(|(start, len)| Range {
-                start,
-                end: start + len,
-            })
-            .collect(),
-    ))
-}
-
-// too slow (2:06 min), probably because of seeds loop
-// try to implement and analyze this https://github.com/hyper-neutrino/advent-of-code/blob/main/2023/day05p2.py
-fn lowest_location_part2(s: &str) -> u64 {
-    let (s, seeds) = parse_seeds_range(s).unwrap();
-    let (_, maps) = parse_input(s).unwrap();
-    let mut res = u64::MAX;
-    for seed_range in seeds {
-        for seed in seed_range {
-            let mut loc = seed;
-            for map in &maps {
-                loc = map.map(loc);
-            }
-            if loc < res {
-                res = loc;
-            }
-        }
-    }
-    res
-}
-
-fn main() {
+fn main() -> ExitCode {
     env_logger::init();
-    let s = get_file_string();
-    println!("part1 {}", lowest_location_part1(&s));
-    println!("part2 {}", lowest_location_part2(&s));
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn parser() {
-        let (_, (dst, src)) = parse_range("50 98 2").unwrap();
-        assert_eq!(dst, 50..52);
-        assert_eq!(src, 98..100);
-
-        let (_, ranges) = parse_map("50 98 2\n50 98 2\n").unwrap();
-        assert_eq!(ranges.0.len(), 2);
-
-        let (_, ranges) = parse_map("50 98 2\n50 98 2").unwrap();
-        assert_eq!(ranges.0.len(), 2);
-    }
+    run_inputs(|s| {
+        println!("{}", solve::<Fertilizer>(s, &[1, 2])?);
+        Ok(())
+    })
 }
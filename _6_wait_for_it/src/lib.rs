@@ -0,0 +1,291 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, not_line_ending, space0, u64},
+    combinator::{cut, eof, opt, verify},
+    error::context,
+    multi::many1,
+    sequence::{preceded, terminated},
+};
+use utils::parse::{finish, IResult};
+use utils::{Error, Result, Solution};
+
+#[derive(Debug)]
+pub struct Race {
+    time: u64,
+    dist: u64,
+}
+
+fn parse_line<'a>(s: &'a str, t: &'static str) -> IResult<'a, Vec<u64>> {
+    let (s, _) = context(t, tag(t))(s)?;
+    let (s, nums) = many1(preceded(space0, u64))(s)?;
+    let (s, _) = cut(context(
+        "number or end of line",
+        terminated(space0, alt((line_ending, eof))),
+    ))(s)?;
+    Ok((s, nums))
+}
+
+fn parse_input(s: &str) -> IResult<'_, (Vec<u64>, Vec<u64>)> {
+    let (s, times) = parse_line(s, "Time:")?;
+    let (s, dists) = parse_line(s, "Distance:")?;
+    Ok((s, (times, dists)))
+}
+
+// every time needs its distance, extra numbers on either line are an error
+fn races(times: Vec<u64>, dists: Vec<u64>) -> Result<Vec<Race>> {
+    if times.len() != dists.len() {
+        return Err(Error::Parse(format!(
+            "{} times but {} distances",
+            times.len(),
+            dists.len()
+        )));
+    }
+    Ok(times
+        .into_iter()
+        .zip(dists)
+        .map(|(t, d)| Race { time: t, dist: d })
+        .collect())
+}
+
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // newton iteration starting above the root converges from above
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+fn travelled(time: u128, press_time: u128) -> u128 {
+    (time - press_time) * press_time
+}
+
+impl Race {
+    // all math is done in u128 where t^2 and 4d can't overflow, the
+    // resulting count is at most t + 1 so it fits back into u64
+    //
+    // press times p with p * (t - p) > d lie strictly between the roots
+    // (t -+ sqrt(t^2 - 4d)) / 2, integer sqrt gives the bound up to one
+    // step which is fixed by checking neighbours exactly
+    fn wins(&self) -> u64 {
+        let (t, d) = (self.time as u128, self.dist as u128);
+        let half = t / 2;
+        if travelled(t, half) <= d {
+            return 0;
+        }
+
+        let disc = t * t - 4 * d;
+        let mut lo = (t - isqrt(disc).min(t)) / 2;
+        while travelled(t, lo) <= d {
+            lo += 1;
+        }
+        while lo > 0 && travelled(t, lo - 1) > d {
+            lo -= 1;
+        }
+        // winning press times are symmetric around t / 2
+        (t - 2 * lo + 1) as u64
+    }
+}
+
+fn every_possible_race_solution_part1(races: &[Race]) -> Result<u64> {
+    let mut res: u64 = 1;
+    for race in races {
+        let wins = race.wins();
+        res = res
+            .checked_mul(if wins > 0 { wins } else { 1 })
+            .ok_or_else(|| {
+                Error::Overflow("product of race wins does not fit into u64".to_string())
+            })?;
+    }
+    Ok(res)
+}
+
+// digits of the line with the spaces between them dropped
+fn parse_line_part2<'a>(s: &'a str, t: &'static str) -> IResult<'a, String> {
+    let (s, _) = context(t, tag(t))(s)?;
+    let (s, line) = terminated(
+        context(
+            "digits and spaces",
+            verify(not_line_ending, |l: &str| {
+                l.chars().all(|c| c.is_ascii_digit() || c == ' ')
+                    && l.chars().any(|c| c.is_ascii_digit())
+            }),
+        ),
+        opt(line_ending),
+    )(s)?;
+    Ok((s, line.chars().filter(|c| c.is_ascii_digit()).collect()))
+}
+
+fn parse_input_part2(s: &str) -> IResult<'_, (String, String)> {
+    let (s, time) = parse_line_part2(s, "Time:")?;
+    let (s, dist) = parse_line_part2(s, "Distance:")?;
+
+    Ok((s, (time, dist)))
+}
+
+fn kerned(digits: &str) -> Result<u64> {
+    digits
+        .parse()
+        .map_err(|_| Error::Overflow(format!("{} does not fit into u64", digits)))
+}
+
+fn wins_count_part2(race: &Race) -> u64 {
+    // debug!("race {:?}", race);
+    race.wins()
+}
+
+// part2 reads the same sheet with spaces between digits ignored
+#[derive(Debug)]
+pub struct Races {
+    races: Vec<Race>,
+    kerned: Race,
+}
+
+pub struct WaitForIt;
+
+impl Solution for WaitForIt {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    type Parsed = Races;
+
+    fn parse(input: &str) -> Result<Races> {
+        let (times, dists) = finish(input, parse_input)?;
+        let races = races(times, dists)?;
+        let (time, dist) = finish(input, parse_input_part2)?;
+        let kerned = Race {
+            time: kerned(&time)?,
+            dist: kerned(&dist)?,
+        };
+        Ok(Races { races, kerned })
+    }
+
+    fn part1(races: &Races) -> Result<u64> {
+        every_possible_race_solution_part1(&races.races)
+    }
+
+    fn part2(races: &Races) -> Result<u64> {
+        Ok(wins_count_part2(&races.kerned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_win_possible(time: u64, max_dist: u64, press_time: u64) -> bool {
+        (time - press_time) * press_time > max_dist
+    }
+
+    fn wins_brute(time: u64, dist: u64) -> u64 {
+        (0..=time)
+            .filter(|&p| is_win_possible(time, dist, p))
+            .count() as u64
+    }
+
+    #[test]
+    fn integer_sqrt() {
+        for n in 0..10_000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({})", n);
+        }
+        let big = (u64::MAX as u128) * (u64::MAX as u128);
+        assert_eq!(isqrt(big), u64::MAX as u128);
+        assert_eq!(isqrt(big - 1), u64::MAX as u128 - 1);
+    }
+
+    #[test]
+    fn overflow() {
+        let race = Race {
+            time: u64::MAX,
+            dist: u64::MAX,
+        };
+        assert!(race.wins() > 0);
+
+        let (_, (time, dist)) =
+            parse_input_part2("Time:   7  15   30\nDistance:  9  40  200\n").unwrap();
+        assert_eq!(kerned(&time).unwrap(), 71530);
+        assert_eq!(kerned(&dist).unwrap(), 940200);
+        // leading zeros of a group are kept as written
+        let (_, (time, _)) = parse_input_part2("Time: 7 05\nDistance: 9 40\n").unwrap();
+        assert_eq!(kerned(&time).unwrap(), 705);
+
+        let input = "Time: 18446744073709551615 1\nDistance: 1 1\n";
+        assert!(matches!(WaitForIt::parse(input), Err(Error::Overflow(_))));
+
+        let races: Vec<_> = (0..5)
+            .map(|_| Race {
+                time: 1 << 20,
+                dist: 0,
+            })
+            .collect();
+        assert!(matches!(
+            every_possible_race_solution_part1(&races),
+            Err(Error::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn malformed_races() {
+        let syntax = |s| match WaitForIt::parse(s) {
+            Err(Error::Syntax(e)) => (e.line, e.column, e.expected),
+            _ => panic!("expected syntax error for {:?}", s),
+        };
+        assert_eq!(
+            syntax("Time: 7 x 30\nDistance: 9 40 200\n"),
+            (1, 9, "number or end of line".to_string())
+        );
+        assert_eq!(
+            syntax("Time: 7 15\nDistanse: 9 40\n"),
+            (2, 1, "Distance:".to_string())
+        );
+        assert_eq!(
+            syntax("Time: 7 15\nDistance: 9 40\nTime: 1\n"),
+            (3, 1, "end of input".to_string())
+        );
+
+        let err = WaitForIt::parse("Time: 7 15 30\nDistance: 9 40\n").unwrap_err();
+        assert_eq!(err.to_string(), "parse error: 3 times but 2 distances");
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force() {
+        for time in 0..=100 {
+            for dist in 0..=2600 {
+                let race = Race { time, dist };
+                assert_eq!(race.wins(), wins_brute(time, dist), "{:?}", race);
+            }
+        }
+    }
+
+    #[test]
+    fn closed_form_matches_enumeration() {
+        const MAX_DIST: usize = 3000;
+        for time in 0..=3000u64 {
+            // wins[d] counts press times beating distance d, built by
+            // enumerating every press time once
+            let mut diff = vec![0i64; MAX_DIST + 2];
+            for p in 0..=time {
+                let travelled = ((time - p) * p) as usize;
+                diff[0] += 1;
+                diff[travelled.min(MAX_DIST + 1)] -= 1;
+            }
+            let mut wins = 0;
+            for (dist, delta) in diff.iter().take(MAX_DIST + 1).enumerate() {
+                wins += delta;
+                let race = Race {
+                    time,
+                    dist: dist as u64,
+                };
+                assert_eq!(race.wins(), wins as u64, "{:?}", race);
+            }
+        }
+    }
+}
@@ -0,0 +1,942 @@
+#[cfg(test)]
+#[allow(dead_code)]
+mod priority_queue;
+
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use utils::{Error, Result, Solution};
+
+// part 1 plays classic rules, part 2 turns the wildcards into the weakest
+// cards that also count as whatever makes the hand strongest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Ruleset {
+    Classic,
+    Joker,
+}
+
+impl FromStr for Ruleset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "classic" => Ok(Ruleset::Classic),
+            "joker" => Ok(Ruleset::Joker),
+            _ => Err(Error::Parse(format!(
+                "unknown ruleset `{}`, expected classic or joker",
+                s
+            ))),
+        }
+    }
+}
+
+// names of the five card groupings, weakest first
+#[derive(PartialEq, PartialOrd, Eq, Ord, Debug, Clone, Copy)]
+pub enum HandStrength {
+    High = 0, // 23456
+    One,      // A23A4
+    Two,      // 23432
+    Three,    // TTT98
+    Full,     // 23332
+    Four,     // AA8AA
+    Five,     // AAAAA
+}
+
+impl HandStrength {
+    // other hand sizes group in ways without a name
+    pub fn from_groups(groups: &[usize]) -> Option<HandStrength> {
+        match groups {
+            [1, 1, 1, 1, 1] => Some(HandStrength::High),
+            [2, 1, 1, 1] => Some(HandStrength::One),
+            [2, 2, 1] => Some(HandStrength::Two),
+            [3, 1, 1] => Some(HandStrength::Three),
+            [3, 2] => Some(HandStrength::Full),
+            [4, 1] => Some(HandStrength::Four),
+            [5] => Some(HandStrength::Five),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HandStrength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandStrength::High => "high card",
+            HandStrength::One => "one pair",
+            HandStrength::Two => "two pair",
+            HandStrength::Three => "three of a kind",
+            HandStrength::Full => "full house",
+            HandStrength::Four => "four of a kind",
+            HandStrength::Five => "five of a kind",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const CARDS: &str = "23456789TJQKA";
+
+// every card rank takes one nibble of the sort key
+const MAX_DECK_SIZE: usize = 16;
+// seven cards still group 15 ways, so the strength fits the top nibble
+const MAX_HAND_SIZE: usize = 7;
+
+// card symbols from weakest to strongest, the cards that act as wildcards
+// under joker rules and the number of cards in a hand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<char>,
+    wildcards: Vec<char>,
+    hand_size: usize,
+    // card order under joker rules, wildcards first
+    joker_order: Vec<char>,
+    // every way a hand splits into groups of equal cards, group sizes
+    // descending, from the weakest hand to the strongest
+    strengths: Vec<Vec<usize>>,
+}
+
+impl Deck {
+    pub fn new(cards: &str, wildcards: &str, hand_size: usize) -> Result<Deck> {
+        let bad = |msg: String| Err(Error::Parse(msg));
+        let cards: Vec<char> = cards.chars().collect();
+        if cards.is_empty() {
+            return bad("deck has no cards".to_string());
+        }
+        if cards.len() > MAX_DECK_SIZE {
+            return bad(format!(
+                "deck has {} cards, at most {} are supported",
+                cards.len(),
+                MAX_DECK_SIZE
+            ));
+        }
+        if cards.iter().any(|c| c.is_whitespace()) {
+            return bad("card symbols can't be whitespace".to_string());
+        }
+        if let Some(c) = cards.iter().duplicates().next() {
+            return bad(format!("card `{}` appears twice in the deck", c));
+        }
+        let wildcards: Vec<char> = wildcards.chars().unique().collect();
+        if let Some(c) = wildcards.iter().find(|c| !cards.contains(c)) {
+            return bad(format!("wildcard `{}` is not in the deck", c));
+        }
+        if !(1..=MAX_HAND_SIZE).contains(&hand_size) {
+            return bad(format!(
+                "hand size {} is not supported, expected 1 to {}",
+                hand_size, MAX_HAND_SIZE
+            ));
+        }
+
+        let (wild, plain): (Vec<char>, Vec<char>) =
+            cards.iter().partition(|c| wildcards.contains(c));
+        let mut strengths = groupings(hand_size, hand_size);
+        strengths.sort_unstable();
+        Ok(Deck {
+            joker_order: wild.into_iter().chain(plain).collect(),
+            cards,
+            wildcards,
+            hand_size,
+            strengths,
+        })
+    }
+
+    // the puzzle deck, J is the joker
+    pub fn standard() -> Deck {
+        Deck::new(CARDS, "J", 5).unwrap()
+    }
+
+    pub fn cards(&self) -> String {
+        self.cards.iter().collect()
+    }
+
+    pub fn wildcards(&self) -> String {
+        self.wildcards.iter().collect()
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    fn order(&self, rules: Ruleset) -> &[char] {
+        match rules {
+            Ruleset::Classic => &self.cards,
+            Ruleset::Joker => &self.joker_order,
+        }
+    }
+
+    fn rank(&self, c: char, rules: Ruleset) -> Option<u32> {
+        self.order(rules)
+            .iter()
+            .position(|&o| o == c)
+            .map(|r| r as u32)
+    }
+
+    fn is_wild(&self, c: char, rules: Ruleset) -> bool {
+        rules == Ruleset::Joker && self.wildcards.contains(&c)
+    }
+
+    // card counts sorted descending, wildcards join the biggest group since
+    // that always gives the strongest hand, then the position of those
+    // counts among all groupings ordered like the counts themselves
+    fn strength(&self, hand: &[char], rules: Ruleset) -> u32 {
+        let wild = hand.iter().filter(|&&c| self.is_wild(c, rules)).count();
+        let mut counts: Vec<usize> = hand
+            .iter()
+            .filter(|&&c| !self.is_wild(c, rules))
+            .counts()
+            .into_values()
+            .collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        match counts.first_mut() {
+            Some(top) => *top += wild,
+            None => counts.push(wild),
+        }
+        self.strengths
+            .binary_search(&counts)
+            .expect("hand of deck size") as u32
+    }
+
+    // group sizes of a strength, [3, 2] is a full house
+    pub fn groups(&self, strength: u32) -> &[usize] {
+        &self.strengths[strength as usize]
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::standard()
+    }
+}
+
+// every way to write n as a sum of parts up to max, parts descending
+fn groupings(n: usize, max: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    (1..=max.min(n))
+        .flat_map(|first| {
+            groupings(n - first, first)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, first);
+                    rest
+                })
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct Hand {
+    cards: String,
+    // index into the deck groupings
+    strength: u32,
+    rules: Ruleset,
+    // strength nibble followed by one nibble per card rank, so comparing
+    // keys compares strength first and then cards left to right
+    key: u32,
+}
+
+impl Hand {
+    fn new(s: &str, deck: &Deck, rules: Ruleset) -> Result<Hand> {
+        let cards: Vec<char> = s.chars().collect();
+        if cards.len() != deck.hand_size {
+            return Err(Error::Parse(format!(
+                "hand `{}` has {} cards, expected {}",
+                s,
+                cards.len(),
+                deck.hand_size
+            )));
+        }
+        let ranks = cards
+            .iter()
+            .map(|&c| {
+                deck.rank(c, rules).ok_or_else(|| {
+                    Error::Parse(format!(
+                        "unknown card `{}` in hand `{}`, deck is {}",
+                        c,
+                        s,
+                        deck.cards()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<u32>>>()?;
+        let strength = deck.strength(&cards, rules);
+        let key = ranks.iter().fold(strength, |key, r| key << 4 | r);
+        Ok(Hand {
+            cards: s.to_string(),
+            strength,
+            rules,
+            key,
+        })
+    }
+}
+
+impl Hand {
+    // everything that compares or hashes hands goes through this key, the
+    // packed key is unique per card string within a ruleset since every
+    // card of the deck has its own rank, the ruleset tells the same ranks
+    // under different rules apart
+    fn canonical(&self) -> (Ruleset, u32) {
+        (self.rules, self.key)
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for Hand {}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical().cmp(&other.canonical())
+    }
+}
+
+impl Hash for Hand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HandBid {
+    hand: Hand,
+    bid: u64,
+}
+
+impl HandBid {
+    // equal hands are ordered by bid, so only identical lines tie and
+    // those still take one rank each
+    fn canonical(&self) -> (Ruleset, u32, u64) {
+        let (rules, key) = self.hand.canonical();
+        (rules, key, self.bid)
+    }
+}
+
+impl PartialEq for HandBid {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for HandBid {}
+
+impl PartialOrd for HandBid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HandBid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical().cmp(&other.canonical())
+    }
+}
+
+impl Hash for HandBid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+// blank lines are skipped, errors keep the line number of the input
+fn parse_input(s: &str, deck: &Deck, rules: Ruleset) -> Result<Vec<HandBid>> {
+    let mut res = vec![];
+    for (i, l) in s.lines().enumerate() {
+        if l.trim().is_empty() {
+            continue;
+        }
+        let at_line = |msg: String| Error::Parse(format!("line {}: {}", i + 1, msg));
+        let (hand, bid) = l
+            .split_once(' ')
+            .ok_or_else(|| at_line(format!("expected `hand bid`, got `{}`", l)))?;
+        let hand = Hand::new(hand, deck, rules).map_err(|e| match e {
+            Error::Parse(msg) => at_line(msg),
+            e => e,
+        })?;
+        let bid = bid
+            .parse()
+            .map_err(|_| at_line(format!("bad bid `{}`", bid)))?;
+        res.push(HandBid { hand, bid })
+    }
+    Ok(res)
+}
+
+// ranks hands with a plain integer sort of their keys
+fn total_winnings_sorted(bids: &[HandBid]) -> Result<u64> {
+    let mut keyed: Vec<_> = bids.iter().map(HandBid::canonical).collect();
+    keyed.sort_unstable();
+    keyed
+        .iter()
+        .zip(1u64..)
+        .try_fold(0u64, |total, (&(_, _, bid), rank)| {
+            rank.checked_mul(bid)
+                .and_then(|w| total.checked_add(w))
+                .ok_or_else(|| Error::Overflow("total winnings do not fit into u64".to_string()))
+        })
+}
+
+// one input line at its place in the ranking
+pub struct RankedHand<'a> {
+    pub rank: u64,
+    pub cards: &'a str,
+    pub groups: &'a [usize],
+    pub bid: u64,
+}
+
+impl RankedHand<'_> {
+    // group sizes like 3+3 when the grouping has no name
+    pub fn strength(&self) -> String {
+        match HandStrength::from_groups(self.groups) {
+            Some(s) => s.to_string(),
+            None => self.groups.iter().join("+"),
+        }
+    }
+}
+
+// the same bids scored under both rulesets of one deck
+pub struct Bids {
+    deck: Deck,
+    classic: Vec<HandBid>,
+    joker: Vec<HandBid>,
+}
+
+impl Bids {
+    pub fn parse(input: &str, deck: &Deck) -> Result<Bids> {
+        Ok(Bids {
+            deck: deck.clone(),
+            classic: parse_input(input, deck, Ruleset::Classic)?,
+            joker: parse_input(input, deck, Ruleset::Joker)?,
+        })
+    }
+
+    fn bids(&self, rules: Ruleset) -> &[HandBid] {
+        match rules {
+            Ruleset::Classic => &self.classic,
+            Ruleset::Joker => &self.joker,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.classic.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classic.is_empty()
+    }
+
+    pub fn winnings(&self, rules: Ruleset) -> Result<u64> {
+        total_winnings_sorted(self.bids(rules))
+    }
+
+    // weakest hand first
+    pub fn ranked(&self, rules: Ruleset) -> Vec<RankedHand<'_>> {
+        let mut sorted: Vec<&HandBid> = self.bids(rules).iter().collect();
+        sorted.sort_unstable();
+        sorted
+            .into_iter()
+            .zip(1u64..)
+            .map(|(b, rank)| RankedHand {
+                rank,
+                cards: &b.hand.cards,
+                groups: self.deck.groups(b.hand.strength),
+                bid: b.bid,
+            })
+            .collect()
+    }
+}
+
+pub struct CamelCards;
+
+impl Solution for CamelCards {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    type Parsed = Bids;
+
+    fn parse(input: &str) -> Result<Bids> {
+        Bids::parse(input, &Deck::standard())
+    }
+
+    fn part1(bids: &Bids) -> Result<u64> {
+        bids.winnings(Ruleset::Classic)
+    }
+
+    fn part2(bids: &Bids) -> Result<u64> {
+        bids.winnings(Ruleset::Joker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use priority_queue::Pq;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashSet};
+
+    // earlier ways to rank the bids, kept to check the sorted keys against
+    fn total_winnings(bids: &[HandBid]) -> u64 {
+        let mut pq = Pq::new(bids.len());
+        for b in bids {
+            pq.insert(b.clone());
+        }
+
+        let mut rank = 1u64;
+        let mut res = 0u64;
+        // identical lines come out as one group, each still takes a rank
+        while let Some(ll) = pq.get() {
+            for el in ll {
+                res += rank.saturating_mul(el.bid);
+                rank += 1;
+            }
+        }
+        res
+    }
+
+    fn unique_elems(bids: &[HandBid]) -> bool {
+        let l = bids.len();
+        let mut h = HashSet::new();
+        for b in bids {
+            h.insert(b);
+        }
+        h.len() == l
+    }
+
+    fn total_winnings_variant2(bids: &[HandBid]) -> u64 {
+        let mut pq = BinaryHeap::new();
+        for b in bids {
+            pq.push(Reverse(b.clone()));
+        }
+
+        let mut rank = 1u64;
+        let mut res = 0u64;
+        while let Some(hb) = pq.pop() {
+            res += rank.saturating_mul(hb.0.bid);
+            rank += 1;
+        }
+        res
+    }
+
+    fn hand(s: &str, rules: Ruleset) -> Hand {
+        Hand::new(s, &Deck::standard(), rules).unwrap()
+    }
+
+    fn kind(hand: &Hand) -> HandStrength {
+        HandStrength::from_groups(Deck::standard().groups(hand.strength)).unwrap()
+    }
+
+    #[test]
+    fn parser() {
+        env_logger::init();
+
+        let hand0 = hand("32T3K", Ruleset::Joker);
+        assert_eq!(kind(&hand0), HandStrength::One);
+
+        let hand1 = hand("KK677", Ruleset::Joker);
+        let hand2 = hand("KTJJT", Ruleset::Joker);
+        let hand3 = hand("QQQJA", Ruleset::Joker);
+        assert_eq!(kind(&hand1), HandStrength::Two);
+        assert_eq!(kind(&hand2), HandStrength::Four);
+        assert_eq!(kind(&hand3), HandStrength::Four);
+        assert!(hand2 > hand3 && hand2 > hand1);
+        assert!(hand3 > hand1);
+
+        let deck = Deck::standard();
+        assert_eq!(deck.rank('2', Ruleset::Joker), Some(1));
+        assert_eq!(deck.rank('J', Ruleset::Joker), Some(0));
+        assert_eq!(deck.rank('X', Ruleset::Joker), None);
+
+        let hand1 = hand("JAAAA", Ruleset::Joker);
+        let hand2 = hand("22222", Ruleset::Joker);
+        assert!(hand2 > hand1);
+    }
+
+    #[test]
+    fn classic_rules() {
+        let hand1 = hand("KK677", Ruleset::Classic);
+        let hand2 = hand("KTJJT", Ruleset::Classic);
+        let hand3 = hand("QQQJA", Ruleset::Classic);
+        assert_eq!(kind(&hand2), HandStrength::Two);
+        assert_eq!(kind(&hand3), HandStrength::Three);
+        // same strength, K beats K then K beats T
+        assert!(hand1 > hand2);
+        assert!(hand3 > hand1);
+
+        assert_eq!(Deck::standard().rank('J', Ruleset::Classic), Some(9));
+        assert!(hand("JJJJ2", Ruleset::Classic) > hand("TTTT2", Ruleset::Classic));
+        assert!(hand("JAAAA", Ruleset::Classic) < hand("22222", Ruleset::Classic));
+
+        let bids = CamelCards::parse(include_str!("../test_input")).unwrap();
+        assert_eq!(CamelCards::part1(&bids).unwrap(), 6440);
+        assert_eq!(CamelCards::part2(&bids).unwrap(), 5905);
+    }
+
+    // strength from the number of equal card pairs, it differs for every kind
+    fn pairs_oracle(hand: &[char]) -> HandStrength {
+        let mut pairs = 0;
+        for i in 0..hand.len() {
+            for j in i + 1..hand.len() {
+                pairs += (hand[i] == hand[j]) as u32;
+            }
+        }
+        match pairs {
+            0 => HandStrength::High,
+            1 => HandStrength::One,
+            2 => HandStrength::Two,
+            3 => HandStrength::Three,
+            4 => HandStrength::Full,
+            6 => HandStrength::Four,
+            10 => HandStrength::Five,
+            _ => unreachable!(),
+        }
+    }
+
+    // best strength over every way to replace each joker independently
+    fn joker_oracle(hand: &mut [char], from: usize) -> HandStrength {
+        let Some(i) = (from..hand.len()).find(|&i| hand[i] == 'J') else {
+            return pairs_oracle(hand);
+        };
+        let mut best = HandStrength::High;
+        for c in CARDS.chars().filter(|&c| c != 'J') {
+            hand[i] = c;
+            best = best.max(joker_oracle(hand, i + 1));
+        }
+        hand[i] = 'J';
+        best
+    }
+
+    #[test]
+    fn classifier_matches_oracle() {
+        let deck = Deck::standard();
+        let strength = |hand: &[char], rules| {
+            HandStrength::from_groups(deck.groups(deck.strength(hand, rules)))
+        };
+        let cards: Vec<char> = CARDS.chars().collect();
+        let mut hand = ['2'; 5];
+        for n in 0..13usize.pow(5) {
+            let mut k = n;
+            for c in hand.iter_mut() {
+                *c = cards[k % 13];
+                k /= 13;
+            }
+            let s: String = hand.iter().collect();
+            assert_eq!(
+                strength(&hand, Ruleset::Classic),
+                Some(pairs_oracle(&hand)),
+                "classic {}",
+                s
+            );
+            assert_eq!(
+                strength(&hand, Ruleset::Joker),
+                Some(joker_oracle(&mut hand.clone(), 0)),
+                "joker {}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn packed_keys() {
+        // ranks count from the weakest card of the deck, jokers go first
+        assert_eq!(hand("T55J5", Ruleset::Classic).key, 0x383393);
+        assert_eq!(hand("T55J5", Ruleset::Joker).key, 0x594404);
+
+        for input in [
+            include_str!("../test_input"),
+            include_str!("../test_input2"),
+        ] {
+            let bids = CamelCards::parse(input).unwrap();
+            for bids in [&bids.classic, &bids.joker] {
+                assert_eq!(
+                    total_winnings_sorted(bids).unwrap(),
+                    total_winnings_variant2(bids)
+                );
+            }
+        }
+    }
+
+    // card ranks and ordering Hand had before packed keys and decks,
+    // compares chars one by one
+    fn as_u32(c: &char, rules: Ruleset) -> u32 {
+        match c {
+            l @ '2'..='9' => l.to_digit(10).unwrap(),
+            'T' => 10,
+            'J' if rules == Ruleset::Joker => 1,
+            'J' => 11,
+            'Q' => 12,
+            'K' => 13,
+            'A' => 14,
+            _ => panic!("not supported card"),
+        }
+    }
+
+    #[derive(Clone)]
+    struct CharOrder(HandBid);
+
+    impl PartialEq for CharOrder {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+
+    impl Eq for CharOrder {}
+
+    impl PartialOrd for CharOrder {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for CharOrder {
+        fn cmp(&self, other: &Self) -> Ordering {
+            let (lhs, rhs) = (&self.0.hand, &other.0.hand);
+            if lhs.strength != rhs.strength {
+                return lhs.strength.cmp(&rhs.strength);
+            }
+            let l: Vec<_> = lhs.cards.chars().collect();
+            let r: Vec<_> = rhs.cards.chars().collect();
+            for i in 0..l.len() {
+                match as_u32(&l[i], lhs.rules).cmp(&as_u32(&r[i], rhs.rules)) {
+                    Ordering::Equal => continue,
+                    o => return o,
+                }
+            }
+            Ordering::Equal
+        }
+    }
+
+    fn char_order_heap(bids: &[HandBid]) -> u64 {
+        let mut pq: BinaryHeap<_> = bids.iter().map(|b| Reverse(CharOrder(b.clone()))).collect();
+        let mut rank = 1u64;
+        let mut res = 0u64;
+        while let Some(hb) = pq.pop() {
+            res += rank.saturating_mul(hb.0 .0.bid);
+            rank += 1;
+        }
+        res
+    }
+
+    // cargo test --release -p _7_camel_cards -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn ranking_benchmark() {
+        use utils::bench::{measure, BenchConfig};
+
+        let bids = CamelCards::parse(include_str!("../input")).unwrap();
+        let cfg = BenchConfig::default();
+        let expected = total_winnings_sorted(&bids.joker).unwrap();
+        assert_eq!(char_order_heap(&bids.joker), expected);
+        assert_eq!(total_winnings_variant2(&bids.joker), expected);
+
+        let heap_chars = measure(&cfg, || char_order_heap(&bids.joker));
+        let heap_keys = measure(&cfg, || total_winnings_variant2(&bids.joker));
+        let sorted = measure(&cfg, || total_winnings_sorted(&bids.joker).unwrap());
+        for (name, s) in [
+            ("binary heap, char compare", &heap_chars),
+            ("binary heap, packed keys", &heap_keys),
+            ("sort packed keys", &sorted),
+        ] {
+            println!(
+                "{:<26} median {:>10.2?}  min {:>10.2?}",
+                name, s.median, s.min
+            );
+        }
+    }
+
+    #[test]
+    fn ties() {
+        let hash = |h: &dyn Fn(&mut std::collections::hash_map::DefaultHasher)| {
+            let mut s = std::collections::hash_map::DefaultHasher::new();
+            h(&mut s);
+            s.finish()
+        };
+        let a = hand("KTJJT", Ruleset::Joker);
+        let b = hand("KTJJT", Ruleset::Joker);
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_eq!(hash(&|s| a.hash(s)), hash(&|s| b.hash(s)));
+        assert_ne!(a, hand("KTJJT", Ruleset::Classic));
+        // five of a kind of the second weakest card under either rules
+        let (c, j) = (
+            hand("33333", Ruleset::Classic),
+            hand("22222", Ruleset::Joker),
+        );
+        assert_eq!(c.key, j.key);
+        assert_ne!(c, j);
+        assert_ne!(c.cmp(&j), Ordering::Equal);
+
+        // same hand, different bids: ordered by bid, never equal
+        let input = "KTJJT 5\nKTJJT 3\nKTJJT 5\n32T3K 1\n";
+        let bids = parse_input(input, &Deck::standard(), Ruleset::Joker).unwrap();
+        assert!(bids[1] < bids[0]);
+        assert_eq!(bids[0], bids[2]);
+        assert_eq!(hash(&|s| bids[0].hash(s)), hash(&|s| bids[2].hash(s)));
+        assert!(!unique_elems(&bids));
+        assert!(unique_elems(&bids[..2]));
+        assert!(unique_elems(&bids[2..]));
+
+        // the heap orders by the same key, identical lines take adjacent ranks
+        let mut heap: BinaryHeap<_> = bids.iter().cloned().map(Reverse).collect();
+        let order: Vec<u64> = std::iter::from_fn(|| heap.pop().map(|r| r.0.bid)).collect();
+        assert_eq!(order, vec![1, 3, 5, 5]);
+
+        // Pq groups identical lines but ranks them one by one as well
+        let mut pq = Pq::new(bids.len());
+        for b in &bids {
+            pq.insert(b.clone());
+        }
+        let groups: Vec<Vec<u64>> = std::iter::from_fn(|| pq.get())
+            .map(|g| g.into_iter().map(|b| b.bid).collect())
+            .collect();
+        assert_eq!(groups, vec![vec![1], vec![3], vec![5, 5]]);
+
+        let expected = 1 + 2 * 3 + 3 * 5 + 4 * 5;
+        assert_eq!(total_winnings(&bids), expected);
+        assert_eq!(total_winnings_variant2(&bids), expected);
+        assert_eq!(total_winnings_sorted(&bids).unwrap(), expected);
+    }
+
+    #[test]
+    fn winnings_overflow() {
+        let max = u64::MAX;
+        let overflows = |input: &str| {
+            let bids = CamelCards::parse(input).unwrap();
+            matches!(CamelCards::part1(&bids), Err(Error::Overflow(_)))
+        };
+        // rank times bid
+        assert!(overflows(&format!("32T3K 1\nKK677 {}\n", max)));
+        // sum of fitting winnings
+        assert!(overflows(&format!("32T3K {}\nKK677 1\n", max)));
+        let bids = CamelCards::parse(&format!("32T3K {}\n", max)).unwrap();
+        assert_eq!(CamelCards::part2(&bids).unwrap(), max);
+    }
+
+    #[test]
+    fn decks() {
+        let err = |r: Result<Deck>| match r {
+            Err(Error::Parse(msg)) => msg,
+            _ => panic!("expected a deck error"),
+        };
+        assert_eq!(
+            err(Deck::new("23452", "", 5)),
+            "card `2` appears twice in the deck"
+        );
+        assert_eq!(
+            err(Deck::new(CARDS, "X", 5)),
+            "wildcard `X` is not in the deck"
+        );
+        assert!(err(Deck::new(CARDS, "J", 0)).starts_with("hand size 0"));
+        assert!(err(Deck::new(CARDS, "J", 8)).starts_with("hand size 8"));
+        assert!(err(Deck::new("0123456789ABCDEFG", "", 5)).starts_with("deck has 17 cards"));
+        assert!(Deck::new("0123456789ABCDEF", "", 7).is_ok());
+
+        // one strength per way to group the hand
+        let counts: Vec<usize> = (1..=7)
+            .map(|n| Deck::new(CARDS, "", n).unwrap().strengths.len())
+            .collect();
+        assert_eq!(counts, vec![1, 2, 3, 5, 7, 11, 15]);
+    }
+
+    #[test]
+    fn ranked_hands() {
+        let bids = CamelCards::parse(include_str!("../test_input")).unwrap();
+        let ranked = bids.ranked(Ruleset::Joker);
+        let order: Vec<(u64, &str, u64)> =
+            ranked.iter().map(|r| (r.rank, r.cards, r.bid)).collect();
+        assert_eq!(
+            order,
+            vec![
+                (1, "32T3K", 765),
+                (2, "KK677", 28),
+                (3, "T55J5", 684),
+                (4, "QQQJA", 483),
+                (5, "KTJJT", 220)
+            ]
+        );
+        assert_eq!(ranked[0].strength(), "one pair");
+        assert_eq!(ranked[4].strength(), "four of a kind");
+        let total: u64 = ranked.iter().map(|r| r.rank * r.bid).sum();
+        assert_eq!(total, bids.winnings(Ruleset::Joker).unwrap());
+
+        assert_eq!("joker".parse::<Ruleset>().unwrap(), Ruleset::Joker);
+        assert!("poker".parse::<Ruleset>().is_err());
+    }
+
+    #[test]
+    fn custom_decks() {
+        let msg = |input: &str, deck: &Deck| match Bids::parse(input, deck) {
+            Err(Error::Parse(msg)) => msg,
+            _ => panic!("expected parse error for {:?}", input),
+        };
+
+        // unknown cards are reported, not a panic
+        let deck = Deck::standard();
+        assert_eq!(
+            msg("32T3K 765\n2345X 1\n", &deck),
+            "line 2: unknown card `X` in hand `2345X`, deck is 23456789TJQKA"
+        );
+        assert_eq!(
+            msg("2345 1\n", &deck),
+            "line 1: hand `2345` has 4 cards, expected 5"
+        );
+        assert_eq!(
+            msg("23456\n", &deck),
+            "line 1: expected `hand bid`, got `23456`"
+        );
+        assert_eq!(msg("23456 x\n", &deck), "line 1: bad bid `x`");
+
+        // blank lines are skipped like on the other days
+        let bids = Bids::parse("32T3K 765\n\nKK677 28\n\n", &deck).unwrap();
+        assert_eq!(bids.len(), 2);
+        assert_eq!(
+            msg("32T3K 765\n\n2345X 1\n", &deck),
+            "line 3: unknown card `X` in hand `2345X`, deck is 23456789TJQKA"
+        );
+
+        // short deck without twos to fives
+        let short = Deck::new("6789TJQKA", "", 5).unwrap();
+        assert_eq!(
+            msg("22345 1\n", &short),
+            "line 1: unknown card `2` in hand `22345`, deck is 6789TJQKA"
+        );
+        let bids = Bids::parse("AKQJT 1\n66789 2\n", &short).unwrap();
+        assert_eq!(bids.winnings(Ruleset::Classic).unwrap(), 1 + 2 * 2);
+        // no wildcards, joker rules change nothing
+        assert_eq!(bids.winnings(Ruleset::Joker).unwrap(), 1 + 2 * 2);
+
+        // six card hands group eleven ways, two triples lose to four of a kind
+        let six = Deck::new(CARDS, "J", 6).unwrap();
+        let strength = |s: &str, rules| {
+            let h = Hand::new(s, &six, rules).unwrap();
+            six.groups(h.strength).to_vec()
+        };
+        assert_eq!(strength("222333", Ruleset::Classic), [3, 3]);
+        assert_eq!(strength("JJ2345", Ruleset::Joker), [3, 1, 1, 1]);
+        assert_eq!(strength("JJ2233", Ruleset::Joker), [4, 2]);
+        let bids = Bids::parse("222333 1\n22223A 2\n224455 4\n", &six).unwrap();
+        assert_eq!(bids.winnings(Ruleset::Classic).unwrap(), 4 + 2 + 3 * 2);
+        let strengths: Vec<String> = bids
+            .ranked(Ruleset::Classic)
+            .iter()
+            .map(|r| r.strength())
+            .collect();
+        assert_eq!(strengths, vec!["2+2+2", "3+3", "4+1+1"]);
+
+        // every wildcard counts as any card and ranks below the plain ones
+        let wild = Deck::new(CARDS, "JQ", 5).unwrap();
+        let jq = |s: &str| Hand::new(s, &wild, Ruleset::Joker).unwrap();
+        assert_eq!(wild.groups(jq("JQ234").strength), [3, 1, 1]);
+        assert_eq!(wild.groups(jq("JQJQ2").strength), [5]);
+        assert!(jq("J2222") < jq("Q2222") && jq("Q2222") < jq("22222"));
+        assert!(jq("KQ2JA") < jq("K2222"));
+    }
+}
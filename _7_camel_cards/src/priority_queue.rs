@@ -147,7 +147,7 @@ impl<T: Ord + Eq + std::fmt::Debug> Pq<T> {
             }
 
             let el = self.data[idx].front().unwrap();
-            let mut replace_with = idx;
+            let replace_with;
             // replace with smaller child
             let left = self.left(idx);
             let replace_with_left =
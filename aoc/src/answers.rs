@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+// answers.toml keeps one table per input file of the day, e.g.
+//
+// [test_input]
+// part1 = 142
+// part2 = 281
+#[derive(Deserialize, Default, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<u64>,
+    pub part2: Option<u64>,
+}
+
+impl Expected {
+    pub fn get(&self, part: u8) -> Option<u64> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+
+    pub fn parts(&self) -> Vec<u8> {
+        [1, 2].into_iter().filter(|&p| self.get(p).is_some()).collect()
+    }
+}
+
+pub type Answers = BTreeMap<String, Expected>;
+
+pub fn parse(s: &str) -> Result<Answers, String> {
+    toml::from_str(s).map_err(|e| e.to_string())
+}
+
+pub fn load(path: &Path) -> Result<Answers, String> {
+    let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse(&s).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = parse("[input]\npart1 = 1\npart2 = 2\n\n[test_input]\npart2 = 281\n").unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers["input"].parts(), vec![1, 2]);
+        assert_eq!(answers["test_input"].get(1), None);
+        assert_eq!(answers["test_input"].get(2), Some(281));
+
+        assert!(parse("[input]\npart3 = 1\n").is_err());
+    }
+}
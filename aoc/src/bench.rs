@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use utils::bench::{PhaseStats, Stats};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub phase: String,
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+    pub mean_ns: u64,
+}
+
+fn ns(d: Duration) -> u64 {
+    d.as_nanos() as u64
+}
+
+impl Record {
+    pub fn new(day: u8, ps: &PhaseStats) -> Self {
+        let Stats {
+            iterations,
+            min,
+            median,
+            p90,
+            p99,
+            max,
+            mean,
+        } = ps.stats;
+        Record {
+            day,
+            phase: ps.phase.to_string(),
+            iterations,
+            min_ns: ns(min),
+            median_ns: ns(median),
+            p90_ns: ns(p90),
+            p99_ns: ns(p99),
+            max_ns: ns(max),
+            mean_ns: ns(mean),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Results {
+    pub records: Vec<Record>,
+}
+
+impl Results {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        serde_json::from_str(&s).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let s = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, s + "\n").map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn find(&self, day: u8, phase: &str) -> Option<&Record> {
+        self.records.iter().find(|r| r.day == day && r.phase == phase)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+// relative change of the median in percent and whether it is beyond threshold
+pub fn compare(current: &Record, baseline: &Record, threshold: f64) -> (f64, Verdict) {
+    let base = baseline.median_ns.max(1) as f64;
+    let change = (current.median_ns as f64 - base) / base * 100.0;
+    let verdict = if change > threshold {
+        Verdict::Regressed
+    } else if change < -threshold {
+        Verdict::Improved
+    } else {
+        Verdict::Unchanged
+    };
+    (change, verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(median_ns: u64) -> Record {
+        Record {
+            day: 5,
+            phase: "part2".to_string(),
+            iterations: 10,
+            min_ns: median_ns,
+            median_ns,
+            p90_ns: median_ns,
+            p99_ns: median_ns,
+            max_ns: median_ns,
+            mean_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn compare_with_baseline() {
+        let (change, verdict) = compare(&record(130), &record(100), 10.0);
+        assert_eq!(verdict, Verdict::Regressed);
+        assert!((change - 30.0).abs() < 1e-9);
+        assert_eq!(compare(&record(105), &record(100), 10.0).1, Verdict::Unchanged);
+        assert_eq!(compare(&record(50), &record(100), 10.0).1, Verdict::Improved);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let results = Results {
+            records: vec![record(42)],
+        };
+        let s = serde_json::to_string(&results).unwrap();
+        let back: Results = serde_json::from_str(&s).unwrap();
+        assert_eq!(back.find(5, "part2"), Some(&record(42)));
+        assert_eq!(back.find(5, "part1"), None);
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+
+use utils::{day, Day, Solution};
+
+pub struct Entry {
+    pub day: Box<dyn Day>,
+    dir: &'static str,
+}
+
+impl Entry {
+    fn new<S: Solution + 'static>(dir: &'static str) -> Self {
+        Entry { day: day::<S>(), dir }
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        workspace_root().join(self.dir)
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        self.dir().join("input")
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir().join("answers.toml")
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+pub fn all() -> Vec<Entry> {
+    vec![
+        Entry::new::<_1_trebuchet::Trebuchet>("_1_trebuchet"),
+        Entry::new::<_2_cube_conundrum::CubeConundrum>("_2_cube_conundrum"),
+        Entry::new::<_3_gear_ratios::GearRatios>("_3_gear_ratios"),
+        Entry::new::<_4_scratchcards::Scratchcards>("_4_scratchcards"),
+        Entry::new::<_5_fertilizer::Fertilizer>("_5_fertilizer"),
+        Entry::new::<_6_wait_for_it::WaitForIt>("_6_wait_for_it"),
+        Entry::new::<_7_camel_cards::CamelCards>("_7_camel_cards"),
+    ]
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+
+use _7_camel_cards::Deck;
+use serde::Deserialize;
+
+// a deck file changes the puzzle deck field by field, e.g.
+//
+// cards = "6789TJQKA"
+// wildcards = "JQ"
+// hand_size = 6
+#[derive(Deserialize, Default, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct DeckConfig {
+    pub cards: Option<String>,
+    pub wildcards: Option<String>,
+    pub hand_size: Option<usize>,
+}
+
+impl DeckConfig {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        toml::from_str(s).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&s).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    // fields set in `over` win
+    pub fn merge(self, over: DeckConfig) -> DeckConfig {
+        DeckConfig {
+            cards: over.cards.or(self.cards),
+            wildcards: over.wildcards.or(self.wildcards),
+            hand_size: over.hand_size.or(self.hand_size),
+        }
+    }
+
+    // the standard wildcards only come with the standard cards, a deck of
+    // its own has none unless it names them
+    pub fn build(&self) -> utils::Result<Deck> {
+        let standard = Deck::standard();
+        let (cards, wildcards) = match &self.cards {
+            Some(cards) => (cards.clone(), String::new()),
+            None => (standard.cards(), standard.wildcards()),
+        };
+        Deck::new(
+            &cards,
+            self.wildcards.as_deref().unwrap_or(&wildcards),
+            self.hand_size.unwrap_or(standard.hand_size()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deck_config() {
+        assert_eq!(DeckConfig::default().build().unwrap(), Deck::standard());
+
+        let file = DeckConfig::parse("cards = \"6789TJQKA\"\nhand_size = 6\n").unwrap();
+        let flags = DeckConfig {
+            wildcards: Some("".to_string()),
+            hand_size: Some(5),
+            ..Default::default()
+        };
+        let deck = file.merge(flags).build().unwrap();
+        assert_eq!(deck.cards(), "6789TJQKA");
+        assert_eq!(deck.wildcards(), "");
+        assert_eq!(deck.hand_size(), 5);
+
+        // own cards without wildcards, like --cards alone
+        let deck = DeckConfig {
+            cards: Some("6789TQKA".to_string()),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+        assert_eq!(deck.cards(), "6789TQKA");
+        assert_eq!(deck.wildcards(), "");
+        let deck = DeckConfig {
+            hand_size: Some(6),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+        assert_eq!(deck.wildcards(), "J");
+
+        assert!(DeckConfig::parse("jokers = \"J\"\n").is_err());
+        assert!(DeckConfig::parse("wildcards = \"X\"\n")
+            .unwrap()
+            .build()
+            .is_err());
+    }
+}
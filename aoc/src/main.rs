@@ -0,0 +1,619 @@
+mod answers;
+mod bench;
+mod days;
+mod deck;
+mod table;
+mod verify;
+
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand};
+use table::Table;
+use utils::bench::BenchConfig;
+use utils::{Error, InputSource, Report};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day or all of them and print answers with timings
+    Run(RunArgs),
+    /// Check answers of every known input against answers.toml
+    Verify(VerifyArgs),
+    /// Benchmark parse and both parts on the real input (use --release)
+    Bench(BenchArgs),
+    /// Sum day 3 gear values under a custom gear rule
+    Gears(GearsArgs),
+    /// Print the day 4 scratchcard cascade card by card
+    Cards(CardsArgs),
+    /// Score day 7 hands with a custom deck
+    Camel(CamelArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day number or `all`
+    #[arg(value_parser = parse_selector)]
+    day: Selector,
+    /// Run only this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input file (`-` for stdin), defaults to the day's `input`
+    #[arg(long)]
+    input: Option<String>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day number or `all`
+    #[arg(value_parser = parse_selector, default_value = "all")]
+    day: Selector,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day number or `all`
+    #[arg(value_parser = parse_selector, default_value = "all")]
+    day: Selector,
+    /// Warm-up time per phase in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    warmup_ms: u64,
+    /// Measurement time budget per phase in milliseconds
+    #[arg(long, default_value_t = 3000)]
+    measure_ms: u64,
+    /// Maximum number of measured iterations per phase
+    #[arg(long, default_value_t = 100)]
+    iterations: usize,
+    /// Where to write the JSON results
+    #[arg(long, default_value = "bench.json")]
+    output: PathBuf,
+    /// Previous results to compare medians against
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Median change in percent that counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Args)]
+struct GearsArgs {
+    /// Input file (`-` for stdin), defaults to day 3 `input`
+    #[arg(long)]
+    input: Option<String>,
+    /// Symbols that can be gears
+    #[arg(long, default_value = "*")]
+    symbols: String,
+    /// Required number of adjacent parts, `N` or `N-M`
+    #[arg(long, default_value = "2", value_parser = _3_gear_ratios::parse_arity)]
+    parts: RangeInclusive<usize>,
+    /// How parts are combined: product, sum or max
+    #[arg(long, default_value = "product")]
+    reduce: _3_gear_ratios::Reduction,
+    /// Read numbers horizontal, vertical or both
+    #[arg(long, default_value = "horizontal")]
+    orientation: _3_gear_ratios::Orientation,
+    /// Print every matching gear
+    #[arg(long)]
+    list: bool,
+    /// Render the annotated schematic as ansi or html
+    #[arg(long)]
+    render: Option<_3_gear_ratios::render::Format>,
+    /// Write the rendered schematic to this file instead of stdout
+    #[arg(long, requires = "render")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct CardsArgs {
+    /// Input file (`-` for stdin), defaults to day 4 `input`
+    #[arg(long)]
+    input: Option<String>,
+    /// What to do with copies won past the last card: clamp, error or extend
+    #[arg(long, default_value = "error")]
+    past_end: _4_scratchcards::PastEnd,
+    /// Print CSV instead of a table
+    #[arg(long)]
+    csv: bool,
+}
+
+#[derive(Args)]
+struct CamelArgs {
+    /// Input file (`-` for stdin), defaults to day 7 `input`
+    #[arg(long)]
+    input: Option<String>,
+    /// TOML file with `cards`, `wildcards` and `hand_size`
+    #[arg(long)]
+    deck: Option<PathBuf>,
+    /// Card symbols from weakest to strongest, overrides the deck file
+    #[arg(long)]
+    cards: Option<String>,
+    /// Cards that are wild under joker rules, overrides the deck file
+    #[arg(long)]
+    wildcards: Option<String>,
+    /// Number of cards in a hand, overrides the deck file
+    #[arg(long)]
+    hand_size: Option<usize>,
+    /// Print every hand by rank under classic or joker rules
+    #[arg(long)]
+    list: Option<_7_camel_cards::Ruleset>,
+}
+
+#[derive(Clone, Copy)]
+enum Selector {
+    All,
+    Day(u8),
+}
+
+impl Selector {
+    fn entries(self) -> Vec<days::Entry> {
+        days::all()
+            .into_iter()
+            .filter(|e| match self {
+                Selector::All => true,
+                Selector::Day(d) => e.day.day() == d,
+            })
+            .collect()
+    }
+}
+
+fn parse_selector(s: &str) -> Result<Selector, String> {
+    if s == "all" {
+        return Ok(Selector::All);
+    }
+    s.parse()
+        .map(Selector::Day)
+        .map_err(|_| format!("expected day number or `all`, got `{}`", s))
+}
+
+fn fmt_duration(d: Duration) -> String {
+    format!("{:.2?}", d)
+}
+
+fn push_report(table: &mut Table, report: &Report) -> bool {
+    let day = report.day.to_string();
+    table.push(vec![
+        day.clone(),
+        report.title.to_string(),
+        "parse".to_string(),
+        String::new(),
+        fmt_duration(report.parse),
+    ]);
+
+    let mut ok = true;
+    for part in &report.parts {
+        let answer = match &part.answer {
+            Ok(a) => a.to_string(),
+            Err(e) => {
+                ok &= matches!(e, Error::Unsolved);
+                format!("error: {}", e)
+            }
+        };
+        table.push(vec![
+            day.clone(),
+            report.title.to_string(),
+            part.part.to_string(),
+            answer,
+            fmt_duration(part.elapsed),
+        ]);
+    }
+    ok
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let entries = args.day.entries();
+    if entries.is_empty() {
+        eprintln!("error: no such day");
+        return ExitCode::FAILURE;
+    }
+    if args.input.is_some() && entries.len() > 1 {
+        eprintln!("error: --input can be used only with a single day");
+        return ExitCode::FAILURE;
+    }
+
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+
+    let mut table = Table::new(&["day", "title", "part", "answer", "time"]);
+    let mut ok = true;
+    for entry in entries {
+        let source = match &args.input {
+            Some(arg) => InputSource::from_arg(arg),
+            None => InputSource::File(entry.input_path()),
+        };
+        let input = match source.read() {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("error: day {}: {}", entry.day.day(), e);
+                ok = false;
+                continue;
+            }
+        };
+        match entry.day.run(&input, &parts) {
+            Ok(report) => ok &= push_report(&mut table, &report),
+            Err(e) => {
+                eprintln!("error: day {}: {}", entry.day.day(), e);
+                ok = false;
+            }
+        }
+    }
+
+    if !table.is_empty() {
+        print!("{}", table);
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn verify(args: VerifyArgs) -> ExitCode {
+    let entries = args.day.entries();
+    if entries.is_empty() {
+        eprintln!("error: no such day");
+        return ExitCode::FAILURE;
+    }
+
+    let mut table = Table::new(&["day", "input", "part", "expected", "actual"]);
+    let mut total = 0;
+    let mut ok = true;
+    for entry in entries {
+        let checks = match verify::verify(&entry, |_| true) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("error: day {}: {}", entry.day.day(), e);
+                ok = false;
+                continue;
+            }
+        };
+        total += checks.len();
+        for c in checks.into_iter().filter(|c| !c.ok()) {
+            table.push(vec![
+                c.day.to_string(),
+                c.input,
+                c.part.to_string(),
+                format!("-{}", c.expected),
+                match c.actual {
+                    Ok(a) => format!("+{}", a),
+                    Err(e) => format!("+error: {}", e),
+                },
+            ]);
+        }
+    }
+
+    if table.is_empty() {
+        println!("all {} answers match", total);
+    } else {
+        print!("{}", table);
+        ok = false;
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let entries = args.day.entries();
+    if entries.is_empty() {
+        eprintln!("error: no such day");
+        return ExitCode::FAILURE;
+    }
+    let baseline = match args
+        .baseline
+        .as_deref()
+        .map(bench::Results::load)
+        .transpose()
+    {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let cfg = BenchConfig {
+        warmup: Duration::from_millis(args.warmup_ms),
+        measure: Duration::from_millis(args.measure_ms),
+        iterations: args.iterations.max(1),
+    };
+
+    let mut header = vec![
+        "day", "phase", "iters", "min", "median", "p90", "p99", "max",
+    ];
+    if baseline.is_some() {
+        header.extend(["baseline", "change"]);
+    }
+    let mut table = Table::new(&header);
+    let mut results = bench::Results::default();
+    let mut ok = true;
+    for entry in entries {
+        let day = entry.day.day();
+        let phases = InputSource::File(entry.input_path())
+            .read()
+            .map_err(Error::from)
+            .and_then(|input| entry.day.bench(&input, &cfg));
+        let phases = match phases {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("error: day {}: {}", day, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        for ps in &phases {
+            let record = bench::Record::new(day, ps);
+            let s = &ps.stats;
+            let mut row = vec![
+                day.to_string(),
+                ps.phase.to_string(),
+                s.iterations.to_string(),
+                fmt_duration(s.min),
+                fmt_duration(s.median),
+                fmt_duration(s.p90),
+                fmt_duration(s.p99),
+                fmt_duration(s.max),
+            ];
+            if let Some(baseline) = &baseline {
+                match baseline.find(day, ps.phase) {
+                    Some(base) => {
+                        let (change, verdict) = bench::compare(&record, base, args.threshold);
+                        let mark = match verdict {
+                            bench::Verdict::Regressed => {
+                                ok = false;
+                                " regressed"
+                            }
+                            bench::Verdict::Improved => " improved",
+                            bench::Verdict::Unchanged => "",
+                        };
+                        row.push(fmt_duration(Duration::from_nanos(base.median_ns)));
+                        row.push(format!("{:+.1}%{}", change, mark));
+                    }
+                    None => row.extend([String::new(), "new".to_string()]),
+                }
+            }
+            table.push(row);
+            results.records.push(record);
+        }
+    }
+
+    if !table.is_empty() {
+        print!("{}", table);
+    }
+    if let Err(e) = results.save(&args.output) {
+        eprintln!("error: {}", e);
+        return ExitCode::FAILURE;
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn gears(args: GearsArgs) -> ExitCode {
+    use _3_gear_ratios::{render, GearRule, Reduction, Schematic};
+
+    let source = match &args.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => InputSource::File(Selector::Day(3).entries()[0].input_path()),
+    };
+    let rule = GearRule {
+        symbols: args.symbols.chars().collect(),
+        parts: args.parts,
+        reduction: args.reduce,
+    };
+    let gears = source
+        .read()
+        .map_err(Error::from)
+        .and_then(|input| Schematic::parse_with(&input, args.orientation))
+        .and_then(|schematic| {
+            let rendered = args
+                .render
+                .map(|format| render::render(&schematic, &rule, format))
+                .transpose()?;
+            Ok((schematic.gears(&rule)?, rendered))
+        });
+    let gears = match gears {
+        Ok((g, rendered)) => {
+            match (rendered, &args.output) {
+                (Some(r), Some(path)) => {
+                    if let Err(e) = fs::write(path, r) {
+                        eprintln!("error: {}: {}", path.display(), e);
+                        return ExitCode::FAILURE;
+                    }
+                }
+                (Some(r), None) => print!("{}", r),
+                (None, _) => {}
+            }
+            g
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.list {
+        let mut table = Table::new(&["line", "column", "symbol", "parts", "value"]);
+        for g in &gears {
+            let parts: Vec<_> = g.parts.iter().map(|p| p.to_string()).collect();
+            table.push(vec![
+                (g.pos.row + 1).to_string(),
+                (g.pos.col + 1).to_string(),
+                g.ch.to_string(),
+                parts.join(" "),
+                g.value.to_string(),
+            ]);
+        }
+        if !table.is_empty() {
+            print!("{}", table);
+        }
+    }
+    match Reduction::Sum.apply(gears.iter().map(|g| g.value)) {
+        Ok(total) => {
+            println!("{} gears, total {}", gears.len(), total);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cards(args: CardsArgs) -> ExitCode {
+    use _4_scratchcards::{CardCascade, Scratchcards};
+    use utils::Solution;
+
+    let source = match &args.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => InputSource::File(Selector::Day(4).entries()[0].input_path()),
+    };
+    let cascade = source
+        .read()
+        .map_err(Error::from)
+        .and_then(|input| Scratchcards::parse(&input))
+        .and_then(|cards| CardCascade::new(&cards, args.past_end));
+    let cascade = match cascade {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut table = Table::new(&["card", "matches", "instances", "won", "received"]);
+    for c in cascade.iter() {
+        table.push(vec![
+            c.id.to_string(),
+            c.matches.to_string(),
+            c.instances.to_string(),
+            c.copies_won.to_string(),
+            c.copies_received.to_string(),
+        ]);
+    }
+    if args.csv {
+        print!("{}", table.csv());
+        return ExitCode::SUCCESS;
+    }
+    print!("{}", table);
+    match cascade.total() {
+        Ok(total) => {
+            println!("{} cards, {} instances", cascade.len(), total);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn camel(args: CamelArgs) -> ExitCode {
+    use _7_camel_cards::{Bids, Ruleset};
+    use deck::DeckConfig;
+
+    let file = match &args.deck {
+        Some(path) => match DeckConfig::load(path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => DeckConfig::default(),
+    };
+    let flags = DeckConfig {
+        cards: args.cards,
+        wildcards: args.wildcards,
+        hand_size: args.hand_size,
+    };
+    let deck = match file.merge(flags).build() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let source = match &args.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => InputSource::File(Selector::Day(7).entries()[0].input_path()),
+    };
+    let bids = source
+        .read()
+        .map_err(Error::from)
+        .and_then(|input| Bids::parse(&input, &deck));
+    let bids = match bids {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(rules) = args.list {
+        let mut table = Table::new(&["rank", "hand", "strength", "bid"]);
+        for r in bids.ranked(rules) {
+            table.push(vec![
+                r.rank.to_string(),
+                r.cards.to_string(),
+                r.strength(),
+                r.bid.to_string(),
+            ]);
+        }
+        print!("{}", table);
+        println!();
+    }
+
+    let mut table = Table::new(&["rules", "winnings"]);
+    for (name, rules) in [("classic", Ruleset::Classic), ("joker", Ruleset::Joker)] {
+        match bids.winnings(rules) {
+            Ok(w) => table.push(vec![name.to_string(), w.to_string()]),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    print!("{}", table);
+    println!(
+        "{} hands of {} from {}, wildcards {}",
+        bids.len(),
+        deck.hand_size(),
+        deck.cards(),
+        if deck.wildcards().is_empty() {
+            "none".to_string()
+        } else {
+            deck.wildcards()
+        }
+    );
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+        Command::Gears(args) => gears(args),
+        Command::Cards(args) => cards(args),
+        Command::Camel(args) => camel(args),
+    }
+}
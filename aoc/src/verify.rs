@@ -0,0 +1,72 @@
+use crate::answers;
+use crate::days::Entry;
+use utils::read_input;
+
+pub struct Check {
+    pub day: u8,
+    pub input: String,
+    pub part: u8,
+    pub expected: u64,
+    pub actual: Result<u64, String>,
+}
+
+impl Check {
+    pub fn ok(&self) -> bool {
+        self.actual.as_ref() == Ok(&self.expected)
+    }
+}
+
+// runs every part that has a known answer against every input listed in
+// the day's answers.toml
+pub fn verify(entry: &Entry, mut filter: impl FnMut(&str) -> bool) -> Result<Vec<Check>, String> {
+    let day = entry.day.day();
+    let answers = answers::load(&entry.answers_path())?;
+    let mut checks = vec![];
+    for (name, expected) in answers.iter().filter(|(name, _)| filter(name)) {
+        let input = read_input(entry.dir().join(name)).map_err(|e| e.to_string())?;
+        let parts = expected.parts();
+        let results: Vec<_> = match entry.day.run(&input, &parts) {
+            Ok(report) => report
+                .parts
+                .into_iter()
+                .map(|p| p.answer.map_err(|e| e.to_string()))
+                .collect(),
+            Err(e) => parts.iter().map(|_| Err(e.to_string())).collect(),
+        };
+        for (part, actual) in parts.into_iter().zip(results) {
+            checks.push(Check {
+                day,
+                input: name.clone(),
+                part,
+                expected: expected.get(part).unwrap(),
+                actual,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_inputs() {
+        for entry in days::all() {
+            let checks = verify(&entry, |name| name.starts_with("test_input")).unwrap();
+            assert!(!checks.is_empty(), "no test answers for day {}", entry.day.day());
+            for c in checks {
+                assert!(
+                    c.ok(),
+                    "day {} {} part{}: expected {} got {:?}",
+                    c.day,
+                    c.input,
+                    c.part,
+                    c.expected,
+                    c.actual
+                );
+            }
+        }
+    }
+}
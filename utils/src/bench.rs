@@ -0,0 +1,141 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::{Error, Result, Solution};
+
+#[derive(Clone, Debug)]
+pub struct BenchConfig {
+    pub warmup: Duration,
+    // measuring stops at whichever limit is hit first, but every phase
+    // is measured at least once
+    pub measure: Duration,
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: Duration::from_secs(1),
+            measure: Duration::from_secs(3),
+            iterations: 100,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let n = samples.len();
+        // nearest rank percentile
+        let pct = |p: usize| samples[(p * n).div_ceil(100).clamp(1, n) - 1];
+        Stats {
+            iterations: n,
+            min: samples[0],
+            median: pct(50),
+            p90: pct(90),
+            p99: pct(99),
+            max: samples[n - 1],
+            mean: samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+pub struct PhaseStats {
+    pub phase: &'static str,
+    pub stats: Stats,
+}
+
+pub fn measure<T>(cfg: &BenchConfig, mut f: impl FnMut() -> T) -> Stats {
+    let start = Instant::now();
+    loop {
+        black_box(f());
+        if start.elapsed() >= cfg.warmup {
+            break;
+        }
+    }
+
+    let mut samples = Vec::with_capacity(cfg.iterations);
+    let start = Instant::now();
+    while samples.is_empty() || (samples.len() < cfg.iterations && start.elapsed() < cfg.measure) {
+        let now = Instant::now();
+        black_box(f());
+        samples.push(now.elapsed());
+    }
+    Stats::from_samples(samples)
+}
+
+type PartFn<S> = fn(&<S as Solution>::Parsed) -> Result<u64>;
+
+// parts that are not solved yet are skipped, any other error aborts
+// benchmarking of the day
+pub fn bench<S: Solution>(input: &str, cfg: &BenchConfig) -> Result<Vec<PhaseStats>> {
+    let parsed = S::parse(input)?;
+    let mut res = vec![PhaseStats {
+        phase: "parse",
+        stats: measure(cfg, || S::parse(black_box(input))),
+    }];
+
+    for (phase, part) in [("part1", S::part1 as PartFn<S>), ("part2", S::part2)] {
+        match part(&parsed) {
+            Ok(_) => {}
+            Err(Error::Unsolved) => continue,
+            Err(e) => return Err(e),
+        }
+        res.push(PhaseStats {
+            phase,
+            stats: measure(cfg, || part(black_box(&parsed))),
+        });
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let samples = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.iterations, 100);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(50));
+        assert_eq!(stats.p90, Duration::from_micros(90));
+        assert_eq!(stats.p99, Duration::from_micros(99));
+        assert_eq!(stats.max, Duration::from_micros(100));
+        assert_eq!(stats.mean, Duration::from_nanos(50_500));
+
+        let stats = Stats::from_samples(vec![Duration::from_millis(3)]);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p99, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn measure_limits() {
+        let cfg = BenchConfig {
+            warmup: Duration::ZERO,
+            measure: Duration::from_secs(60),
+            iterations: 7,
+        };
+        assert_eq!(measure(&cfg, || 1).iterations, 7);
+
+        let cfg = BenchConfig {
+            warmup: Duration::ZERO,
+            measure: Duration::ZERO,
+            iterations: 7,
+        };
+        assert_eq!(measure(&cfg, || 1).iterations, 1);
+    }
+}
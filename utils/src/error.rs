@@ -0,0 +1,85 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::input::InputSource;
+use crate::parse::ParseError;
+
+#[derive(Debug)]
+pub enum InputError {
+    MissingArgument,
+    NotFound(PathBuf),
+    Io(InputSource, io::Error),
+    Decompress(InputSource, io::Error),
+    NotUtf8(InputSource),
+    Empty(InputSource),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::MissingArgument => write!(f, "missing input file argument"),
+            InputError::NotFound(p) => write!(f, "input file {} not found", p.display()),
+            InputError::Io(src, e) => write!(f, "can't read {}: {}", src, e),
+            InputError::Decompress(src, e) => write!(f, "can't decompress {}: {}", src, e),
+            InputError::NotUtf8(src) => write!(f, "input {} is not valid utf-8", src),
+            InputError::Empty(src) => write!(f, "input {} is empty", src),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io(_, e) | InputError::Decompress(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Input(InputError),
+    Parse(String),
+    Syntax(ParseError),
+    Solve(String),
+    Overflow(String),
+    UnknownPart(u8),
+    Unsolved,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Input(e) => write!(f, "{}", e),
+            Error::Parse(msg) => write!(f, "parse error: {}", msg),
+            Error::Syntax(e) => write!(f, "parse error: {}", e),
+            Error::Solve(msg) => write!(f, "{}", msg),
+            Error::Overflow(msg) => write!(f, "arithmetic overflow: {}", msg),
+            Error::UnknownPart(p) => write!(f, "unknown part {}", p),
+            Error::Unsolved => write!(f, "not solved yet"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(e) => Some(e),
+            Error::Syntax(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(e: InputError) -> Self {
+        Error::Input(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Syntax(e)
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Point { row, col }
+    }
+}
+
+// (row, col) offsets
+pub const DIRS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const DIRS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+// row whose length differs from the first one, line is 1-based
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RaggedRow {
+    pub line: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RaggedRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: row has {} cells, expected {}",
+            self.line, self.found, self.expected
+        )
+    }
+}
+
+impl std::error::Error for RaggedRow {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "grid size mismatch");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, RaggedRow> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if let Some((i, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(RaggedRow {
+                line: i + 1,
+                expected: width,
+                found: r.len(),
+            });
+        }
+        Ok(Grid::new(
+            width,
+            height,
+            rows.into_iter().flatten().collect(),
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.row < self.height && p.col < self.width
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if !self.contains(p) {
+            return None;
+        }
+        self.cells.get(p.row * self.width + p.col)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if !self.contains(p) {
+            return None;
+        }
+        self.cells.get_mut(p.row * self.width + p.col)
+    }
+
+    // p moved by (drow, dcol) if it stays inside the grid
+    pub fn offset(&self, p: Point, (drow, dcol): (isize, isize)) -> Option<Point> {
+        let row = p.row.checked_add_signed(drow)?;
+        let col = p.col.checked_add_signed(dcol)?;
+        let p = Point::new(row, col);
+        self.contains(p).then_some(p)
+    }
+
+    pub fn neighbours<'a>(
+        &'a self,
+        p: Point,
+        dirs: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        dirs.iter().filter_map(move |&d| self.offset(p, d))
+    }
+
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(p, &DIRS4)
+    }
+
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(p, &DIRS8)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |r| self.row(r))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |c| self.column(c))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| Point::new(r, c)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    // points connected to start through cells matching pred, moving only
+    // in given directions, sorted by row and column
+    pub fn region(
+        &self,
+        start: Point,
+        dirs: &[(isize, isize)],
+        pred: impl Fn(&T) -> bool,
+    ) -> Vec<Point> {
+        if !self.get(start).is_some_and(&pred) {
+            return vec![];
+        }
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            for n in self.neighbours(p, dirs) {
+                if pred(&self[n]) && seen.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        let mut res: Vec<_> = seen.into_iter().collect();
+        res.sort();
+        res
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(s: &str) -> Result<Self, RaggedRow> {
+        Grid::from_rows(s.lines().map(|l| l.chars().collect()).collect())
+    }
+}
+
+impl<T> std::ops::Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).expect("point out of grid")
+    }
+}
+
+impl<T> std::ops::IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p).expect("point out of grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "467.\n..*.\n.35.\n";
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.to_string(), TEXT);
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&'*'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 4)), None);
+        assert_eq!(
+            Grid::parse("ab\ncd\ne\n"),
+            Err(RaggedRow {
+                line: 3,
+                expected: 2,
+                found: 1
+            })
+        );
+        let wide = Grid::parse("abc\ndef\n").unwrap();
+        assert_eq!((wide.width(), wide.height()), (3, 2));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::parse(TEXT).unwrap();
+        let corner: Vec<_> = grid.neighbours8(Point::new(0, 0)).collect();
+        assert_eq!(
+            corner,
+            vec![Point::new(0, 1), Point::new(1, 1), Point::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours4(Point::new(2, 3)).count(), 2);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!(grid.row(2), &['.', '3', '5', '.']);
+        let col: String = grid.column(2).collect();
+        assert_eq!(col, "7*5");
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.columns().count(), 4);
+        let (p, c) = grid.iter().nth(6).unwrap();
+        assert_eq!((p, *c), (Point::new(1, 2), '*'));
+    }
+
+    #[test]
+    fn region() {
+        let grid = Grid::parse(TEXT).unwrap();
+        let horizontal = [(0, -1), (0, 1)];
+        let num = grid.region(Point::new(0, 1), &horizontal, |c| c.is_ascii_digit());
+        assert_eq!(
+            num,
+            vec![Point::new(0, 0), Point::new(0, 1), Point::new(0, 2)]
+        );
+        let dots = grid.region(Point::new(1, 0), &DIRS4, |c| *c == '.');
+        assert_eq!(dots.len(), 3);
+        assert!(grid
+            .region(Point::new(1, 2), &DIRS4, |c| *c == '.')
+            .is_empty());
+    }
+}
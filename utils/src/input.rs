@@ -0,0 +1,204 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use flate2::read::MultiGzDecoder;
+
+use crate::{Error, InputError};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        let bytes = match self {
+            InputSource::Stdin => {
+                let mut buf = vec![];
+                io::stdin()
+                    .read_to_end(&mut buf)
+                    .map_err(|e| InputError::Io(self.clone(), e))?;
+                buf
+            }
+            InputSource::File(p) => fs::read(p).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => InputError::NotFound(p.clone()),
+                _ => InputError::Io(self.clone(), e),
+            })?,
+        };
+        let bytes = decompress(bytes).map_err(|e| InputError::Decompress(self.clone(), e))?;
+        let s = String::from_utf8(bytes).map_err(|_| InputError::NotUtf8(self.clone()))?;
+        if s.trim().is_empty() {
+            return Err(InputError::Empty(self.clone()));
+        }
+        Ok(s)
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(p) => write!(f, "{}", p.display()),
+        }
+    }
+}
+
+fn decompress(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    if bytes.starts_with(GZIP_MAGIC) {
+        let mut out = vec![];
+        MultiGzDecoder::new(&bytes[..]).read_to_end(&mut out)?;
+        Ok(out)
+    } else if bytes.starts_with(ZSTD_MAGIC) {
+        zstd::decode_all(&bytes[..])
+    } else {
+        Ok(bytes)
+    }
+}
+
+fn sources_from(args: impl IntoIterator<Item = String>) -> Result<Vec<InputSource>, InputError> {
+    let sources: Vec<_> = args
+        .into_iter()
+        .map(|a| InputSource::from_arg(&a))
+        .collect();
+    if sources.is_empty() {
+        return Err(InputError::MissingArgument);
+    }
+    Ok(sources)
+}
+
+fn load_first(args: impl IntoIterator<Item = String>) -> Result<String, InputError> {
+    sources_from(args)?[0].read()
+}
+
+pub fn input_sources() -> Result<Vec<InputSource>, InputError> {
+    sources_from(env::args().skip(1))
+}
+
+// first input given in args, for binaries that only take one
+pub fn load_input() -> Result<String, InputError> {
+    load_first(env::args().skip(1))
+}
+
+pub fn read_input(fp: impl AsRef<Path>) -> Result<String, InputError> {
+    InputSource::File(fp.as_ref().to_path_buf()).read()
+}
+
+// runs f on every input given in args, printing a header per input
+// when there are several of them
+pub fn run_inputs(mut f: impl FnMut(&str) -> Result<(), Error>) -> ExitCode {
+    let sources = match input_sources() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let headers = sources.len() > 1;
+    let mut code = ExitCode::SUCCESS;
+    for (i, source) in sources.iter().enumerate() {
+        if headers {
+            if i > 0 {
+                println!();
+            }
+            println!("==> {} <==", source);
+        }
+        match source.read().map_err(Error::from).and_then(|s| f(&s)) {
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("error: {}", e);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn read_input_errors() {
+        let dir = env::temp_dir().join(format!("utils-read-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing");
+        assert!(matches!(read_input(&missing), Err(InputError::NotFound(_))));
+
+        let empty = dir.join("empty");
+        fs::write(&empty, "\n\n").unwrap();
+        assert!(matches!(read_input(&empty), Err(InputError::Empty(_))));
+
+        let binary = dir.join("binary");
+        fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+        assert!(matches!(read_input(&binary), Err(InputError::NotUtf8(_))));
+
+        let ok = dir.join("ok");
+        fs::write(&ok, "1abc2\n").unwrap();
+        assert_eq!(read_input(&ok).unwrap(), "1abc2\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compressed() {
+        let text = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+        let mut gz = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        gz.write_all(text.as_bytes()).unwrap();
+        let gz = gz.finish().unwrap();
+        assert!(gz.starts_with(GZIP_MAGIC));
+        assert_eq!(decompress(gz).unwrap(), text.as_bytes());
+
+        let zst = zstd::encode_all(text.as_bytes(), 0).unwrap();
+        assert!(zst.starts_with(ZSTD_MAGIC));
+        assert_eq!(decompress(zst).unwrap(), text.as_bytes());
+
+        assert_eq!(decompress(text.into()).unwrap(), text.as_bytes());
+    }
+
+    #[test]
+    fn missing_argument() {
+        assert!(matches!(
+            load_first(vec![]),
+            Err(InputError::MissingArgument)
+        ));
+        assert!(matches!(
+            sources_from(vec![]),
+            Err(InputError::MissingArgument)
+        ));
+
+        let fp = env::temp_dir().join(format!("utils-load-first-{}", std::process::id()));
+        fs::write(&fp, "1abc2\n").unwrap();
+        let args = vec![fp.display().to_string(), "other".to_string()];
+        assert_eq!(load_first(args).unwrap(), "1abc2\n");
+        fs::remove_file(&fp).unwrap();
+    }
+
+    #[test]
+    fn source_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("input"),
+            InputSource::File(PathBuf::from("input"))
+        );
+    }
+}
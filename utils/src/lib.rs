@@ -1,12 +1,10 @@
-use std::env;
-use std::fs::File;
-use std::io::prelude::*;
+pub mod bench;
+mod error;
+pub mod grid;
+mod input;
+pub mod parse;
+mod solution;
 
-pub fn get_file_string() -> String {
-    let args = env::args().collect::<Vec<String>>();
-    let fp = args.get(1).unwrap();
-    let mut f = File::open(fp).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-    s
-}
+pub use error::{Error, InputError};
+pub use input::{input_sources, load_input, read_input, run_inputs, InputSource};
+pub use solution::{day, solve, Day, PartReport, Report, Result, Solution};
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+// nom result type used by the day parsers, keeps contexts for error messages
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+// position in the input where parsing failed, line and column are 1-based,
+// item names what was being parsed there when the caller knows it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+    pub item: Option<String>,
+}
+
+impl ParseError {
+    // rest must be a suffix of input
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = input.len() - rest.len();
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            text: input[start..end].trim_end_matches('\r').to_string(),
+            expected: expected.into(),
+            item: None,
+        }
+    }
+
+    pub fn within(mut self, item: impl Into<String>) -> Self {
+        self.item = Some(item.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(item) = &self.item {
+            write!(f, "{}: ", item)?;
+        }
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>1$}", "^", self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(kind: &ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "number".to_string(),
+        ErrorKind::Alpha => "letters".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "space".to_string(),
+        ErrorKind::CrLf => "line ending".to_string(),
+        ErrorKind::Eof => "end of input".to_string(),
+        k => k.description().to_lowercase(),
+    }
+}
+
+// turns a nom error into a ParseError pointing at the innermost failure,
+// described by the char that was expected there or the nearest context
+fn convert(input: &str, e: VerboseError<&str>) -> ParseError {
+    let Some((rest, kind)) = e.errors.first() else {
+        return ParseError::at(input, input, "valid input");
+    };
+    let context = e.errors.iter().find_map(|(_, k)| match k {
+        VerboseErrorKind::Context(c) => Some(c.to_string()),
+        _ => None,
+    });
+    let expected = match (kind, context) {
+        (VerboseErrorKind::Char(c), _) => format!("`{}`", c.escape_default()),
+        (_, Some(c)) => c,
+        (VerboseErrorKind::Context(c), None) => c.to_string(),
+        (VerboseErrorKind::Nom(k), None) => describe(k),
+    };
+    ParseError::at(input, rest, expected)
+}
+
+// runs parser on the whole input, anything but trailing whitespace left
+// over is an error
+pub fn finish<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, ParseError> {
+    match parser(input) {
+        Ok((rest, out)) => {
+            // point at the first thing that was not consumed
+            let rest = rest.trim_start();
+            if rest.is_empty() {
+                Ok(out)
+            } else {
+                Err(ParseError::at(input, rest, "end of input"))
+            }
+        }
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(convert(input, e)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(input, "", "more input")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        character::complete::{char, newline, u64},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    fn pairs(s: &str) -> IResult<'_, Vec<(u64, u64)>> {
+        separated_list1(
+            newline,
+            context("pair", separated_pair(u64, char(','), u64)),
+        )(s)
+    }
+
+    #[test]
+    fn position() {
+        let e = ParseError::at("ab\ncd\r\nef", "d\r\nef", "x");
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.text, "cd");
+        assert_eq!(e.to_string(), "line 2, column 2: expected x\n  cd\n   ^");
+        let e = e.within("pair 2");
+        assert!(e
+            .to_string()
+            .starts_with("pair 2: line 2, column 2: expected x\n"));
+    }
+
+    #[test]
+    fn finish_errors() {
+        assert_eq!(finish("1,2\n3,4\n", pairs), Ok(vec![(1, 2), (3, 4)]));
+
+        let e = finish("1,2\n3;4\n", pairs).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.expected, "end of input");
+
+        let e = finish("1,2\n3,x\n", pairs).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+
+        let e = finish("1;2\n", pairs).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 2, "`,`"));
+
+        let e = finish("x", pairs).unwrap_err();
+        assert_eq!(e.expected, "pair");
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::bench::{self, BenchConfig, PhaseStats};
+use crate::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<u64>;
+    fn part2(parsed: &Self::Parsed) -> Result<u64>;
+}
+
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<u64>,
+    pub elapsed: Duration,
+}
+
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    pub parse: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+// object safe view of Solution so days with different Parsed types
+// can be stored side by side
+pub trait Day {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn run(&self, input: &str, parts: &[u8]) -> Result<Report>;
+    fn bench(&self, input: &str, cfg: &BenchConfig) -> Result<Vec<PhaseStats>>;
+}
+
+struct Erased<S>(PhantomData<S>);
+
+impl<S: Solution> Day for Erased<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn run(&self, input: &str, parts: &[u8]) -> Result<Report> {
+        solve::<S>(input, parts)
+    }
+
+    fn bench(&self, input: &str, cfg: &BenchConfig) -> Result<Vec<PhaseStats>> {
+        bench::bench::<S>(input, cfg)
+    }
+}
+
+pub fn day<S: Solution + 'static>() -> Box<dyn Day> {
+    Box::new(Erased::<S>(PhantomData))
+}
+
+pub fn solve<S: Solution>(input: &str, parts: &[u8]) -> Result<Report> {
+    let now = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = now.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let now = Instant::now();
+            let answer = match part {
+                1 => S::part1(&parsed),
+                2 => S::part2(&parsed),
+                _ => Err(Error::UnknownPart(part)),
+            };
+            PartReport {
+                part,
+                answer,
+                elapsed: now.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Report {
+        day: S::DAY,
+        title: S::TITLE,
+        parse,
+        parts,
+    })
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match &part.answer {
+                Ok(answer) => write!(f, "part{} {}", part.part, answer)?,
+                Err(e) => write!(f, "part{} error: {}", part.part, e)?,
+            }
+        }
+        Ok(())
+    }
+}